@@ -1,89 +1,3673 @@
+// Most of the Linux readers are unused on Windows, where only stubs are exposed.
+#![cfg_attr(windows, allow(dead_code))]
+
+#[cfg(feature = "byte-unit")]
 use byte_unit::Byte;
-use std::{ffi::CStr, fs::read_to_string, mem::MaybeUninit};
+#[cfg(unix)]
+use std::ffi::{CStr, CString};
+#[cfg(unix)]
+use std::mem::MaybeUninit;
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    fmt,
+    fs::{read_dir, read_to_string},
+    io::{self, Read},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    thread,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+/// Simple macro to convert all bytes to their u8 representation.
+#[cfg(unix)]
+macro_rules! bytes_to_u8 {
+    ($collection:expr) => {
+        $collection
+            .iter()
+            .map(|byte| *byte as u8)
+            .collect::<Vec<_>>()
+    };
+}
+
+/// Whether internal diagnostics are printed to stderr, see `set_logging`.
+static LOGGING: AtomicBool = AtomicBool::new(false);
+
+/// Prints an internal diagnostic to stderr, if logging has been enabled through `set_logging`.
+macro_rules! log {
+    ($($arg:tt)*) => {
+        if LOGGING.load(Ordering::Relaxed) {
+            eprintln!("[lxinfo] {}", format_args!($($arg)*))
+        }
+    };
+}
+
+/// Builds `(name, value)` pairs out of the given `SystemInfo` fields.
+macro_rules! field_pairs {
+    ($info:expr, $($field:ident),* $(,)?) => {
+        vec![$((stringify!($field), $info.$field.to_field_value())),*]
+    };
+}
+
+/// Fetched system information.
+#[derive(Debug)]
+pub struct SystemInfo {
+    pub distro_name: String,
+    pub distro_id: String,
+    pub distro_build_id: String,
+    /// Icon theme name of the distro logo, from the `LOGO` key.
+    pub distro_logo: Option<String>,
+    pub distro_home_url: Option<String>,
+    pub username: String,
+    pub hostname: String,
+    pub shell: String,
+    pub kernel: String,
+    pub uptime_seconds: u32,
+    pub uptime_minutes: u32,
+    pub uptime_hours: u32,
+    pub uptime_days: u32,
+    pub uptime_formatted: String,
+    pub total_mem: String,
+    pub cached_mem: String,
+    pub available_mem: String,
+    pub used_mem: String,
+    /// Container runtime name, and version when the runtime exposes it. `None` on bare metal.
+    pub container_runtime: Option<String>,
+    pub gpu_temp_celsius: Option<f32>,
+    pub batteries: Vec<Battery>,
+    /// Available entropy in bits, from `/proc/sys/kernel/random/entropy_avail`.
+    pub available_entropy: Option<u32>,
+    pub disks: Vec<DiskUsage>,
+    pub bootloader: Option<String>,
+    pub swappiness: Option<u8>,
+    /// DRM card driving the display and its driver, for example `card1 (amdgpu)`.
+    pub primary_gpu: Option<String>,
+    pub cpu_microcode: Option<String>,
+    /// How long ago the system booted, for example `3 hours ago`.
+    pub booted_ago: String,
+    pub root_fs: Option<RootFs>,
+    /// WSL version when running under WSL, `1` or `2`.
+    pub wsl: Option<u8>,
+    pub cpu_family: Option<u32>,
+    pub cpu_model_num: Option<u32>,
+    pub cpu_stepping: Option<u32>,
+    /// Default systemd target, such as `graphical.target`, or `runlevel 3` on sysvinit.
+    pub systemd_target: Option<String>,
+    /// Allocated file descriptors system-wide, from `/proc/sys/fs/file-nr`.
+    pub open_fds: Option<u64>,
+    pub max_fds: Option<u64>,
+    pub dpi: Option<u32>,
+    pub terminal_font: Option<String>,
+    /// Status of each CPU vulnerability, for example `("spectre_v2", "Mitigation: Retpolines")`.
+    pub cpu_mitigations: Vec<(String, String)>,
+    /// When the package manager last changed the system.
+    pub last_update: Option<SystemTime>,
+    /// Installed RAM in bytes according to DMI, including memory reserved by the firmware.
+    pub physical_mem_total: Option<u64>,
+    /// Only fetched when `Config::include_swap` is set.
+    pub total_swap: Option<String>,
+    /// Only fetched when `Config::include_swap` is set.
+    pub used_swap: Option<String>,
+    /// Whether `/` is mounted read-only, as on immutable distros or after an emergency remount.
+    pub root_readonly: bool,
+    /// Number of CPUs that the cgroup may use, for example `1.5`. `None` when unconstrained.
+    pub cpu_quota: Option<f32>,
+    /// Memory that the cgroup may use in bytes. `None` when unlimited.
+    pub mem_limit: Option<u64>,
+    pub monitors: Vec<MonitorInfo>,
+    pub smt_enabled: Option<bool>,
+    /// Active mandatory access control system, SELinux or AppArmor.
+    pub mac_system: Option<MacStatus>,
+    pub interfaces: Vec<NetInterface>,
+    pub keyboard_layout: Option<String>,
+    pub cpu_affinity_count: Option<u32>,
+    /// When this snapshot was taken.
+    pub fetched_at: SystemTime,
+    pub vpn_active: bool,
+    pub vpn_interfaces: Vec<String>,
+    pub cpu_boost: Option<bool>,
+    pub chassis_type: Option<ChassisType>,
+    pub power_profile: Option<String>,
+    pub numa_nodes: u32,
+    /// Memory of each NUMA node in bytes, ordered by node number.
+    pub numa_node_mem: Vec<u64>,
+    /// Performance CPUs of hybrid CPUs, `0` on non-hybrid CPUs.
+    pub cpu_pcores: u32,
+    /// Efficiency CPUs of hybrid CPUs, `0` on non-hybrid CPUs.
+    pub cpu_ecores: u32,
+    /// The 1, 5 and 15 minute load averages divided by the logical CPU count.
+    pub load_per_core: (f32, f32, f32),
+    /// Small ASCII logo of the distro, Tux for distros without a built-in one.
+    pub distro_ascii: &'static str,
+    /// Current frequency of every CPU, ordered by CPU number.
+    pub cpu_freqs_mhz: Vec<f32>,
+    pub boot_time_breakdown: Option<BootTiming>,
+    pub cpu_cstates: Vec<String>,
+    /// Time the system booted at, in seconds since the Unix epoch.
+    pub boot_epoch: Option<u64>,
+    pub thp: Option<String>,
+    /// Number of CPUs that are online, lower than `cpu_possible` when some CPUs are offlined.
+    pub cpu_online: u32,
+    /// Number of CPUs the system supports, including offline ones.
+    pub cpu_possible: u32,
+    /// Reclaimable kernel slab memory, `None` on kernels that don't report it.
+    pub reclaimable_mem: Option<String>,
+    /// Preemption model of the kernel, for example `PREEMPT_RT` on realtime kernels.
+    pub kernel_preempt: Option<String>,
+    pub block_devices: Vec<BlockDevice>,
+    pub default_gateway: Option<IpAddr>,
+    pub fqdn: Option<String>,
+    /// Temperature of every CPU core in degrees Celsius, labeled like `Core 0`.
+    pub cpu_core_temps: Vec<(String, f32)>,
+    /// Number of preallocated hugepages.
+    pub hugepages_total: Option<u64>,
+    pub hugepages_free: Option<u64>,
+    /// Size of a hugepage in bytes.
+    pub hugepage_size: Option<u64>,
+    pub gpu_usage_percent: Option<f32>,
+    /// SGR parameters of the distro color from the `ANSI_COLOR` key, for example `38;2;23;147;209`.
+    pub distro_ansi_color: Option<String>,
+    /// Swap that is also cached in memory, in bytes.
+    pub swap_cached: Option<u64>,
+    /// Memory waiting to be written back to disk, in bytes.
+    pub dirty: Option<u64>,
+    /// Memory being written back to disk, in bytes.
+    pub writeback: Option<u64>,
+    pub on_ac_power: Option<bool>,
+    /// Edition of the distro from the `VARIANT` key, for example `Silverblue`.
+    pub distro_variant: Option<String>,
+    pub distro_variant_id: Option<String>,
+    pub distro_image_id: Option<String>,
+    /// Version of the image on image-based distros, which differs from the distro version.
+    pub distro_image_version: Option<String>,
+    pub flatpak_version: Option<String>,
+    pub snap_version: Option<String>,
+    pub dns_servers: Vec<IpAddr>,
+    /// Boot parameters passed to the kernel, for example `quiet splash nvidia-drm.modeset=1`.
+    pub kernel_cmdline: Option<String>,
+    /// Parameters of `kernel_cmdline`, with the value of `key=value` parameters.
+    pub kernel_cmdline_params: Vec<(String, Option<String>)>,
+    /// Health of the first battery, see `Battery::health_percent`.
+    pub battery_health_percent: Option<f32>,
+    pub ipv6_enabled: bool,
+    pub opengl_renderer: Option<String>,
+    pub vulkan_devices: Vec<String>,
+    pub wm_theme: Option<String>,
+    pub pid_max: Option<u32>,
+    pub firewall: Option<FirewallStatus>,
+    /// Number of logical CPUs sharing each L2 cache.
+    pub l2_shared_by: Option<u32>,
+    /// Number of logical CPUs sharing each L3 cache.
+    pub l3_shared_by: Option<u32>,
+}
+
+/// A blank snapshot, with empty strings, zeroes, `None`s and the Unix epoch as `fetched_at`.
+impl Default for SystemInfo {
+    fn default() -> Self {
+        Self {
+            distro_name: Default::default(),
+            distro_id: Default::default(),
+            distro_build_id: Default::default(),
+            distro_logo: Default::default(),
+            distro_home_url: Default::default(),
+            username: Default::default(),
+            hostname: Default::default(),
+            shell: Default::default(),
+            kernel: Default::default(),
+            uptime_seconds: Default::default(),
+            uptime_minutes: Default::default(),
+            uptime_hours: Default::default(),
+            uptime_days: Default::default(),
+            uptime_formatted: Default::default(),
+            total_mem: Default::default(),
+            cached_mem: Default::default(),
+            available_mem: Default::default(),
+            used_mem: Default::default(),
+            container_runtime: Default::default(),
+            gpu_temp_celsius: Default::default(),
+            batteries: Default::default(),
+            available_entropy: Default::default(),
+            disks: Default::default(),
+            bootloader: Default::default(),
+            swappiness: Default::default(),
+            primary_gpu: Default::default(),
+            cpu_microcode: Default::default(),
+            booted_ago: Default::default(),
+            root_fs: Default::default(),
+            wsl: Default::default(),
+            cpu_family: Default::default(),
+            cpu_model_num: Default::default(),
+            cpu_stepping: Default::default(),
+            systemd_target: Default::default(),
+            open_fds: Default::default(),
+            max_fds: Default::default(),
+            dpi: Default::default(),
+            terminal_font: Default::default(),
+            cpu_mitigations: Default::default(),
+            last_update: Default::default(),
+            physical_mem_total: Default::default(),
+            total_swap: Default::default(),
+            used_swap: Default::default(),
+            root_readonly: Default::default(),
+            cpu_quota: Default::default(),
+            mem_limit: Default::default(),
+            monitors: Default::default(),
+            smt_enabled: Default::default(),
+            mac_system: Default::default(),
+            interfaces: Default::default(),
+            keyboard_layout: Default::default(),
+            cpu_affinity_count: Default::default(),
+            fetched_at: UNIX_EPOCH,
+            vpn_active: Default::default(),
+            vpn_interfaces: Default::default(),
+            cpu_boost: Default::default(),
+            chassis_type: Default::default(),
+            power_profile: Default::default(),
+            numa_nodes: Default::default(),
+            numa_node_mem: Default::default(),
+            cpu_pcores: Default::default(),
+            cpu_ecores: Default::default(),
+            load_per_core: Default::default(),
+            distro_ascii: Default::default(),
+            cpu_freqs_mhz: Default::default(),
+            boot_time_breakdown: Default::default(),
+            cpu_cstates: Default::default(),
+            boot_epoch: Default::default(),
+            thp: Default::default(),
+            cpu_online: Default::default(),
+            cpu_possible: Default::default(),
+            reclaimable_mem: Default::default(),
+            kernel_preempt: Default::default(),
+            block_devices: Default::default(),
+            default_gateway: Default::default(),
+            fqdn: Default::default(),
+            cpu_core_temps: Default::default(),
+            hugepages_total: Default::default(),
+            hugepages_free: Default::default(),
+            hugepage_size: Default::default(),
+            gpu_usage_percent: Default::default(),
+            distro_ansi_color: Default::default(),
+            swap_cached: Default::default(),
+            dirty: Default::default(),
+            writeback: Default::default(),
+            on_ac_power: Default::default(),
+            distro_variant: Default::default(),
+            distro_variant_id: Default::default(),
+            distro_image_id: Default::default(),
+            distro_image_version: Default::default(),
+            flatpak_version: Default::default(),
+            snap_version: Default::default(),
+            dns_servers: Default::default(),
+            kernel_cmdline: Default::default(),
+            kernel_cmdline_params: Default::default(),
+            battery_health_percent: Default::default(),
+            ipv6_enabled: Default::default(),
+            opengl_renderer: Default::default(),
+            vulkan_devices: Default::default(),
+            wm_theme: Default::default(),
+            pid_max: Default::default(),
+            firewall: Default::default(),
+            l2_shared_by: Default::default(),
+            l3_shared_by: Default::default(),
+        }
+    }
+}
+
+/// Memory information from `/proc/meminfo`.
+#[derive(Debug, Clone)]
+pub struct Memory {
+    pub total_mem: String,
+    pub cached_mem: String,
+    pub available_mem: String,
+    pub used_mem: String,
+    pub total_bytes: u64,
+    pub cached_bytes: u64,
+    pub available_bytes: u64,
+    pub used_bytes: u64,
+    pub swap_total_bytes: u64,
+    pub swap_free_bytes: u64,
+    /// Reclaimable kernel slab memory, `None` on kernels that don't report `SReclaimable`.
+    pub reclaimable_mem: Option<String>,
+    pub reclaimable_bytes: Option<u64>,
+}
+
+/// Returns `part / total` clamped to `0.0..=1.0`, or `0.0` when `total` is zero.
+fn ratio(part: u64, total: u64) -> f32 {
+    if total == 0 {
+        return 0.0;
+    }
+
+    (part as f64 / total as f64).clamp(0.0, 1.0) as f32
+}
+
+impl Memory {
+    /// Returns the used share of the memory, from `0.0` to `1.0`, for gauges and progress bars.
+    pub fn usage_ratio(&self) -> f32 {
+        ratio(self.used_bytes, self.total_bytes)
+    }
+
+    /// Returns the used share of the swap, from `0.0` to `1.0`, or `0.0` without swap.
+    pub fn swap_ratio(&self) -> f32 {
+        ratio(
+            self.swap_total_bytes.saturating_sub(self.swap_free_bytes),
+            self.swap_total_bytes,
+        )
+    }
+}
+
+/// Information about a single battery.
+#[derive(Debug, Clone)]
+pub struct Battery {
+    /// Name of the battery, for example `BAT0`.
+    pub name: String,
+    pub percentage: f32,
+    /// Status as reported by the kernel, for example `Charging` or `Discharging`.
+    pub status: String,
+    /// Energy when fully charged, in µWh, for batteries that report energy.
+    pub energy_full: Option<u64>,
+    /// Charge when fully charged, in µAh, for batteries that report charge instead of energy.
+    pub charge_full: Option<u64>,
+    /// Full capacity relative to the design capacity, in percent. Drops as the battery wears.
+    pub health_percent: Option<f32>,
+}
+
+/// A field whose value differs between two `SystemInfo` snapshots.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    pub name: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+/// Formats a `SystemInfo` field value as a `String`.
+trait FieldValue {
+    fn to_field_value(&self) -> String;
+}
+
+impl FieldValue for String {
+    fn to_field_value(&self) -> String {
+        self.clone()
+    }
+}
+
+/// Implements `FieldValue` through `ToString` for the given types.
+macro_rules! impl_field_value {
+    ($($type:ty),*) => {
+        $(impl FieldValue for $type {
+            fn to_field_value(&self) -> String {
+                self.to_string()
+            }
+        })*
+    };
+}
+
+impl_field_value!(bool, u8, u32, u64, f32, &'static str);
+
+/// Missing values are formatted as an empty string.
+impl<T: FieldValue> FieldValue for Option<T> {
+    fn to_field_value(&self) -> String {
+        self.as_ref().map(T::to_field_value).unwrap_or_default()
+    }
+}
+
+impl<T: FieldValue> FieldValue for Vec<T> {
+    fn to_field_value(&self) -> String {
+        self.iter()
+            .map(T::to_field_value)
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl<A: FieldValue, B: FieldValue> FieldValue for (A, B) {
+    fn to_field_value(&self) -> String {
+        format!("{}: {}", self.0.to_field_value(), self.1.to_field_value())
+    }
+}
+
+impl<A: FieldValue, B: FieldValue, C: FieldValue> FieldValue for (A, B, C) {
+    fn to_field_value(&self) -> String {
+        format!(
+            "{} {} {}",
+            self.0.to_field_value(),
+            self.1.to_field_value(),
+            self.2.to_field_value()
+        )
+    }
+}
+
+/// Formatted as seconds since the Unix epoch.
+impl FieldValue for SystemTime {
+    fn to_field_value(&self) -> String {
+        self.duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs()
+            .to_string()
+    }
+}
+
+impl FieldValue for DiskUsage {
+    fn to_field_value(&self) -> String {
+        format!(
+            "{} {}/{} ({})",
+            self.mount_point,
+            bytes_to_gb(self.used_bytes),
+            bytes_to_gb(self.total_bytes),
+            self.fs_type
+        )
+    }
+}
+
+impl FieldValue for RootFs {
+    fn to_field_value(&self) -> String {
+        format!("{} ({})", self.device, self.fs_type)
+    }
+}
+
+impl FieldValue for MonitorInfo {
+    fn to_field_value(&self) -> String {
+        format!("{} {}", self.manufacturer, self.model)
+    }
+}
+
+impl FieldValue for MacStatus {
+    fn to_field_value(&self) -> String {
+        format!("{} ({})", self.kind, self.mode)
+    }
+}
+
+impl FieldValue for IpAddr {
+    fn to_field_value(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl FieldValue for NetInterface {
+    fn to_field_value(&self) -> String {
+        let mut value = self.name.clone();
+        if !self.addresses.is_empty() {
+            value.push_str(&format!(" ({})", self.addresses.to_field_value()));
+        }
+
+        value
+    }
+}
+
+impl FieldValue for BlockDevice {
+    fn to_field_value(&self) -> String {
+        format!("{} {}", self.name, bytes_to_gb(self.size_bytes))
+    }
+}
+
+impl FieldValue for FirewallStatus {
+    fn to_field_value(&self) -> String {
+        let state = if self.active { "active" } else { "inactive" };
+        format!("{} ({state})", self.backend)
+    }
+}
+
+impl FieldValue for BootTiming {
+    fn to_field_value(&self) -> String {
+        format!("{:.3}s", self.total.as_secs_f32())
+    }
+}
+
+impl FieldValue for ChassisType {
+    fn to_field_value(&self) -> String {
+        format!("{self:?}")
+    }
+}
+
+impl FieldValue for Battery {
+    fn to_field_value(&self) -> String {
+        format!("{} {}% ({})", self.name, self.percentage, self.status)
+    }
+}
+
+/// Usage of a mounted filesystem.
+#[derive(Debug, Clone)]
+pub struct DiskUsage {
+    /// Device backing the filesystem, for example `/dev/nvme0n1p2`.
+    pub device: String,
+    pub mount_point: String,
+    pub fs_type: String,
+    pub total_bytes: u64,
+    pub used_bytes: u64,
+    /// Bytes available to unprivileged users.
+    pub available_bytes: u64,
+    pub inodes_total: u64,
+    pub inodes_used: u64,
+    pub inodes_free: u64,
+}
+
+/// The filesystem mounted at `/`.
+#[derive(Debug, Clone)]
+pub struct RootFs {
+    pub device: String,
+    pub fs_type: String,
+    pub mount_options: Vec<String>,
+}
+
+/// Unit system that memory is formatted in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MemUnit {
+    /// Powers of 1000, such as `GB`.
+    #[default]
+    Decimal,
+    /// Powers of 1024, such as `GiB`.
+    Binary,
+}
+
+/// Options controlling how system information is fetched and formatted.
+/// `Config::default()` matches `get_system_information`.
+#[derive(Debug, Clone)]
+pub struct Config {
+    /// Unit system that memory is formatted in.
+    pub mem_unit: MemUnit,
+    /// Number of decimals that memory is formatted with.
+    pub mem_precision: usize,
+    /// Whether to fetch `total_swap` and `used_swap`.
+    pub include_swap: bool,
+    /// Whether to strip ANSI escape sequences and control characters from the fetched strings,
+    /// including those nested in fields such as `monitors`. The built-in `distro_ascii` is kept.
+    /// Defaults to whether the `sanitize` feature is enabled.
+    pub sanitize: bool,
+    /// Whether `interfaces` includes virtual interfaces, such as loopback, bridges and tunnels.
+    pub include_virtual_ifaces: bool,
+    /// Whether to derive the uptime from `boot_epoch` and the current time instead of
+    /// `/proc/uptime`, keeping it consistent with the wall clock.
+    pub uptime_from_boot_epoch: bool,
+    /// Whether to count reclaimable slab memory as available, on top of `MemAvailable`.
+    /// `MemAvailable` only counts part of it, as not all of it can be reclaimed in practice.
+    pub available_includes_reclaimable: bool,
+    /// Whether detectors may run external programs, which is off by default as each of them can
+    /// take up to 3 seconds before it's killed. When disabled, only files are read and
+    /// `gpu_temp_celsius` and `gpu_usage_percent` on NVIDIA GPUs, the runlevel and
+    /// `systemd_target` from `systemctl get-default`, `keyboard_layout` from `localectl`,
+    /// `physical_mem_total`, `power_profile` from power-profiles-daemon, `boot_time_breakdown`,
+    /// the GNOME DPI scaling, the X11 color depth of monitors and the Android version under Termux
+    /// become unavailable, as do `flatpak_version`, `snap_version`, `opengl_renderer`,
+    /// `vulkan_devices` and `firewall` other than ufw.
+    pub allow_subprocess: bool,
+    /// Whether `fqdn` may be resolved through DNS when neither the hostname nor `/etc/hosts`
+    /// contains the domain. Off by default, as the lookup blocks and can stall without a network.
+    pub resolve_fqdn: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            mem_unit: MemUnit::Decimal,
+            mem_precision: 2,
+            include_swap: false,
+            sanitize: cfg!(feature = "sanitize"),
+            include_virtual_ifaces: true,
+            uptime_from_boot_epoch: false,
+            available_includes_reclaimable: false,
+            allow_subprocess: false,
+            resolve_fqdn: false,
+        }
+    }
+}
+
+/// Options for the text output of `SystemInfo::display_with`.
+#[derive(Debug, Clone)]
+pub struct DisplayOptions {
+    /// Names of the fields to show, in order, as in `SystemInfo::as_pairs`.
+    pub fields: Vec<&'static str>,
+    /// Width that labels are padded to, `0` pads them to the longest shown label.
+    pub label_width: usize,
+    /// Whether to start with a `user@host` line.
+    pub header: bool,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            fields: vec![
+                "distro_name",
+                "kernel",
+                "uptime_formatted",
+                "shell",
+                "used_mem",
+                "total_mem",
+            ],
+            label_width: 0,
+            header: true,
+        }
+    }
+}
+
+impl Config {
+    /// Formats kilobytes in the configured unit and precision.
+    fn format_kb(&self, kb: f64) -> String {
+        let (value, unit) = kb_to_parts(kb, self.mem_unit);
+        format!("{value:.*} {unit}", self.mem_precision)
+    }
+}
+
+/// A logged-in user session, from utmp.
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub user: String,
+    /// Terminal of the session, for example `tty1` or `pts/0`.
+    pub tty: String,
+    pub login_time: SystemTime,
+    /// Host that the session was started from, for remote sessions such as SSH.
+    pub remote_host: Option<String>,
+}
+
+/// A connected monitor, parsed from its EDID.
+#[derive(Debug, Clone)]
+pub struct MonitorInfo {
+    /// PNP ID of the manufacturer, for example `DEL` or `SAM`.
+    pub manufacturer: String,
+    /// Name of the monitor, or its product code when it doesn't report one.
+    pub model: String,
+    pub serial: Option<String>,
+    /// Physical size of the image in millimeters, as `(width, height)`.
+    pub size_mm: (u16, u16),
+    /// Bits per pixel across all color channels, for example `24` or `30`.
+    pub color_depth: Option<u8>,
+}
+
+/// A block device, such as a disk or an SSD.
+#[derive(Debug, Clone)]
+pub struct BlockDevice {
+    /// Kernel name of the device, for example `sda` or `nvme0n1`.
+    pub name: String,
+    pub size_bytes: u64,
+    pub model: Option<String>,
+    /// Whether the device has spinning platters, `false` for SSDs.
+    pub rotational: bool,
+}
+
+/// Status of the firewall.
+#[derive(Debug, Clone)]
+pub struct FirewallStatus {
+    /// `ufw`, `firewalld`, `nftables` or `iptables`.
+    pub backend: String,
+    /// Whether the firewall is enabled, or has rules loaded for nftables and iptables.
+    pub active: bool,
+}
+
+/// Time spent in each boot stage, as reported by `systemd-analyze`.
+/// The firmware and loader stages are only known on EFI systems.
+#[derive(Debug, Clone)]
+pub struct BootTiming {
+    pub firmware: Option<Duration>,
+    pub loader: Option<Duration>,
+    pub kernel: Option<Duration>,
+    pub initrd: Option<Duration>,
+    pub userspace: Option<Duration>,
+    pub total: Duration,
+}
+
+/// Status of a mandatory access control system.
+#[derive(Debug, Clone)]
+pub struct MacStatus {
+    /// `SELinux` or `AppArmor`.
+    pub kind: String,
+    /// For example `Enforcing` or `Permissive` for SELinux, `enforce` or `complain` for AppArmor.
+    pub mode: String,
+}
+
+/// Form factor of the machine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChassisType {
+    Desktop,
+    Laptop,
+    Tablet,
+    Server,
+    Vm,
+    Other,
+}
+
+/// A network interface and its addresses.
+#[derive(Debug, Clone)]
+pub struct NetInterface {
+    pub name: String,
+    pub up: bool,
+    /// Whether the interface isn't backed by a physical device, such as `lo` or `wg0`.
+    pub is_virtual: bool,
+    pub addresses: Vec<IpAddr>,
+    /// Negotiated link speed, only known for physical wired interfaces that are up.
+    pub speed_mbps: Option<u32>,
+}
+
+/// Whether a `SystemInfo` field stays the same while the system is running, or changes over time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldKind {
+    /// Only changes after a reboot or configuration change, like the hostname.
+    Static,
+    /// Changes over time, like memory usage and uptime.
+    Volatile,
+}
+
+/// Names of the `SystemInfo` fields that change over time.
+const VOLATILE_FIELDS: &[&str] = &[
+    "uptime_seconds",
+    "uptime_minutes",
+    "uptime_hours",
+    "uptime_days",
+    "uptime_formatted",
+    "cached_mem",
+    "available_mem",
+    "used_mem",
+    "gpu_temp_celsius",
+    "batteries",
+    "available_entropy",
+    "disks",
+    "booted_ago",
+    "open_fds",
+    "used_swap",
+    "fetched_at",
+    "load_per_core",
+    "cpu_freqs_mhz",
+    "reclaimable_mem",
+    "cpu_core_temps",
+    "hugepages_free",
+    "gpu_usage_percent",
+    "swap_cached",
+    "dirty",
+    "writeback",
+    "on_ac_power",
+    "vpn_active",
+    "vpn_interfaces",
+    "interfaces",
+    "default_gateway",
+    "dns_servers",
+    "power_profile",
+    "cpu_boost",
+    "cpu_online",
+    "last_update",
+];
+
+impl SystemInfo {
+    /// Returns every field as a `(name, value)` pair, in declaration order.
+    /// Missing values are represented by an empty string.
+    pub fn as_pairs(&self) -> Vec<(&'static str, String)> {
+        field_pairs!(
+            self,
+            distro_name,
+            distro_id,
+            distro_build_id,
+            distro_logo,
+            distro_home_url,
+            username,
+            hostname,
+            shell,
+            kernel,
+            uptime_seconds,
+            uptime_minutes,
+            uptime_hours,
+            uptime_days,
+            uptime_formatted,
+            total_mem,
+            cached_mem,
+            available_mem,
+            used_mem,
+            container_runtime,
+            gpu_temp_celsius,
+            batteries,
+            available_entropy,
+            disks,
+            bootloader,
+            swappiness,
+            primary_gpu,
+            cpu_microcode,
+            booted_ago,
+            root_fs,
+            wsl,
+            cpu_family,
+            cpu_model_num,
+            cpu_stepping,
+            systemd_target,
+            open_fds,
+            max_fds,
+            dpi,
+            terminal_font,
+            cpu_mitigations,
+            last_update,
+            physical_mem_total,
+            total_swap,
+            used_swap,
+            root_readonly,
+            cpu_quota,
+            mem_limit,
+            monitors,
+            smt_enabled,
+            mac_system,
+            interfaces,
+            keyboard_layout,
+            cpu_affinity_count,
+            fetched_at,
+            vpn_active,
+            vpn_interfaces,
+            cpu_boost,
+            chassis_type,
+            power_profile,
+            numa_nodes,
+            numa_node_mem,
+            cpu_pcores,
+            cpu_ecores,
+            load_per_core,
+            distro_ascii,
+            cpu_freqs_mhz,
+            boot_time_breakdown,
+            cpu_cstates,
+            boot_epoch,
+            thp,
+            cpu_online,
+            cpu_possible,
+            reclaimable_mem,
+            kernel_preempt,
+            block_devices,
+            default_gateway,
+            fqdn,
+            cpu_core_temps,
+            hugepages_total,
+            hugepages_free,
+            hugepage_size,
+            gpu_usage_percent,
+            distro_ansi_color,
+            swap_cached,
+            dirty,
+            writeback,
+            on_ac_power,
+            distro_variant,
+            distro_variant_id,
+            distro_image_id,
+            distro_image_version,
+            flatpak_version,
+            snap_version,
+            dns_servers,
+            kernel_cmdline,
+            kernel_cmdline_params,
+            battery_health_percent,
+            ipv6_enabled,
+            opengl_renderer,
+            vulkan_devices,
+            wm_theme,
+            pid_max,
+            firewall,
+            l2_shared_by,
+            l3_shared_by,
+        )
+    }
+
+    /// Formats every field as a flat `key = value` line, for shell scripts and grep.
+    /// Values containing `=`, quotes or newlines are wrapped in double-quotes and escaped.
+    pub fn to_ini(&self) -> String {
+        let mut ini = String::new();
+        for (name, value) in self.as_pairs() {
+            ini.push_str(name);
+            ini.push_str(" = ");
+            if value.contains(['=', '"', '\\', '\n', '\r']) {
+                ini.push('"');
+                for char in value.chars() {
+                    match char {
+                        '"' => ini.push_str("\\\""),
+                        '\\' => ini.push_str("\\\\"),
+                        '\n' => ini.push_str("\\n"),
+                        '\r' => ini.push_str("\\r"),
+                        _ => ini.push(char),
+                    }
+                }
+                ini.push('"');
+            } else {
+                ini.push_str(&value);
+            }
+            ini.push('\n');
+        }
+
+        ini
+    }
+
+    /// Formats the fields picked by `options` as aligned `label value` lines.
+    /// Fields without a value are skipped.
+    pub fn display_with(&self, options: &DisplayOptions) -> String {
+        let pairs = self.as_pairs();
+        let shown = options
+            .fields
+            .iter()
+            .filter_map(|field| pairs.iter().find(|(name, _)| name == field))
+            .filter(|(_, value)| !value.is_empty())
+            .collect::<Vec<_>>();
+        let width = match options.label_width {
+            0 => shown
+                .iter()
+                .map(|(name, _)| name.len())
+                .max()
+                .unwrap_or_default(),
+            width => width,
+        };
+
+        let mut output = String::new();
+        if options.header {
+            let header = format!("{}@{}", self.username, self.hostname);
+            output.push_str(&header);
+            output.push('\n');
+            output.push_str(&"-".repeat(header.chars().count()));
+            output.push('\n');
+        }
+
+        for (name, value) in shown {
+            output.push_str(&format!("{name:width$} {value}\n"));
+        }
+
+        output
+    }
+
+    /// Returns the fields that changed between `self` and the newer `other` snapshot.
+    pub fn diff(&self, other: &SystemInfo) -> Vec<FieldChange> {
+        self.as_pairs()
+            .into_iter()
+            .zip(other.as_pairs())
+            .filter(|((_, old), (_, new))| old != new)
+            .map(|((name, old), (_, new))| FieldChange { name, old, new })
+            .collect()
+    }
+
+    /// Returns the names of the fields that change over time.
+    /// Refresh loops only have to re-fetch these, the rest can be kept from the first fetch.
+    pub fn volatile_fields() -> &'static [&'static str] {
+        VOLATILE_FIELDS
+    }
+
+    /// Strips ANSI escape sequences and control characters from the user-facing string fields.
+    fn sanitize_fields(&mut self) {
+        for field in [
+            &mut self.distro_name,
+            &mut self.distro_id,
+            &mut self.distro_build_id,
+            &mut self.username,
+            &mut self.hostname,
+            &mut self.shell,
+            &mut self.kernel,
+        ] {
+            *field = sanitize(field);
+        }
+
+        for field in [
+            &mut self.distro_logo,
+            &mut self.distro_home_url,
+            &mut self.container_runtime,
+            &mut self.distro_variant,
+            &mut self.distro_variant_id,
+            &mut self.distro_image_id,
+            &mut self.distro_image_version,
+            &mut self.distro_ansi_color,
+            &mut self.bootloader,
+            &mut self.primary_gpu,
+            &mut self.cpu_microcode,
+            &mut self.systemd_target,
+            &mut self.terminal_font,
+            &mut self.keyboard_layout,
+            &mut self.power_profile,
+            &mut self.thp,
+            &mut self.kernel_preempt,
+            &mut self.fqdn,
+            &mut self.flatpak_version,
+            &mut self.snap_version,
+            &mut self.kernel_cmdline,
+            &mut self.opengl_renderer,
+            &mut self.wm_theme,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            *field = sanitize(field);
+        }
+
+        // Strings nested in lists and structs, such as EDID text and device names.
+        let nested = self
+            .vpn_interfaces
+            .iter_mut()
+            .chain(&mut self.cpu_cstates)
+            .chain(&mut self.vulkan_devices)
+            .chain(
+                self.cpu_mitigations
+                    .iter_mut()
+                    .flat_map(|(name, status)| [name, status]),
+            )
+            .chain(self.cpu_core_temps.iter_mut().map(|(label, _)| label))
+            .chain(
+                self.kernel_cmdline_params
+                    .iter_mut()
+                    .flat_map(|(key, value)| [Some(key), value.as_mut()].into_iter().flatten()),
+            )
+            .chain(self.monitors.iter_mut().flat_map(|monitor| {
+                [
+                    Some(&mut monitor.manufacturer),
+                    Some(&mut monitor.model),
+                    monitor.serial.as_mut(),
+                ]
+                .into_iter()
+                .flatten()
+            }))
+            .chain(
+                self.interfaces
+                    .iter_mut()
+                    .map(|interface| &mut interface.name),
+            )
+            .chain(self.block_devices.iter_mut().flat_map(|device| {
+                [Some(&mut device.name), device.model.as_mut()]
+                    .into_iter()
+                    .flatten()
+            }))
+            .chain(
+                self.batteries
+                    .iter_mut()
+                    .flat_map(|battery| [&mut battery.name, &mut battery.status]),
+            )
+            .chain(
+                self.disks
+                    .iter_mut()
+                    .flat_map(|disk| [&mut disk.device, &mut disk.mount_point, &mut disk.fs_type]),
+            )
+            .chain(self.root_fs.iter_mut().flat_map(|root_fs| {
+                [&mut root_fs.device, &mut root_fs.fs_type]
+                    .into_iter()
+                    .chain(&mut root_fs.mount_options)
+            }))
+            .chain(
+                self.mac_system
+                    .iter_mut()
+                    .flat_map(|mac_system| [&mut mac_system.kind, &mut mac_system.mode]),
+            );
+        for field in nested {
+            *field = sanitize(field);
+        }
+    }
+
+    /// Returns a blank snapshot, for tests and for filling in fields by hand.
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Returns the upstream kernel version from `kernel`, without the distro suffix.
+    /// For example: `6.1.12` for `6.1.12-arch1-1`
+    pub fn kernel_upstream(&self) -> &str {
+        let release = self.kernel.split('-').next().unwrap_or_default();
+        // Some kernels have a fourth version component, such as WSL2's `5.15.90.1`.
+        match release.match_indices('.').nth(2) {
+            Some((index, _)) => &release[..index],
+            None => release,
+        }
+    }
+
+    /// Parses `kernel` into its upstream version, for comparing against a minimum version.
+    /// A missing patch level is read as `0`, for example `6.1` becomes `6.1.0`.
+    pub fn kernel_version(&self) -> Option<KernelVersion> {
+        let mut parts = self.kernel_upstream().split('.');
+        Some(KernelVersion::new(
+            parts.next()?.parse().ok()?,
+            parts.next()?.parse().ok()?,
+            parts.next().map_or(Some(0), |patch| patch.parse().ok())?,
+        ))
+    }
+
+    /// Returns the kind of the given field.
+    /// Unknown field names are treated as `FieldKind::Static`.
+    pub fn field_kind(name: &str) -> FieldKind {
+        if VOLATILE_FIELDS.contains(&name) {
+            FieldKind::Volatile
+        } else {
+            FieldKind::Static
+        }
+    }
+}
+
+/// Formats the system information with the default `DisplayOptions`.
+impl fmt::Display for SystemInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.display_with(&DisplayOptions::default()))
+    }
+}
+
+/// Borrowed view of the os-release fields of `SystemInfo`, for callers that want to avoid
+/// allocating a `String` per field.
+/// Values borrow from the os-release contents passed to `from_os_release`, so the view can't
+/// outlive them. Only values that have to be unescaped or span multiple lines are allocated.
+#[derive(Debug, Clone)]
+pub struct SystemInfoRef<'a> {
+    pub distro_name: Cow<'a, str>,
+    pub distro_id: Cow<'a, str>,
+    pub distro_build_id: Cow<'a, str>,
+    pub distro_logo: Option<Cow<'a, str>>,
+    pub distro_home_url: Option<Cow<'a, str>>,
+    pub distro_ansi_color: Option<Cow<'a, str>>,
+    pub distro_variant: Option<Cow<'a, str>>,
+    pub distro_variant_id: Option<Cow<'a, str>>,
+    pub distro_image_id: Option<Cow<'a, str>>,
+    pub distro_image_version: Option<Cow<'a, str>>,
+}
+
+impl<'a> SystemInfoRef<'a> {
+    /// Parses the view out of the contents of `/etc/os-release`, failing on the same missing keys
+    /// as `get_system_information`.
+    pub fn from_os_release(os_release: &'a str) -> Result<Self, InfoError> {
+        let key = |key| parse_osr_key_ref(os_release, key);
+        let required = |name| key(name).ok_or(InfoError::Missing(name));
+        Ok(Self {
+            distro_name: required("NAME")?,
+            distro_id: required("ID")?,
+            distro_build_id: required("BUILD_ID")?,
+            distro_logo: key("LOGO"),
+            distro_home_url: key("HOME_URL"),
+            distro_ansi_color: key("ANSI_COLOR"),
+            distro_variant: key("VARIANT"),
+            distro_variant_id: key("VARIANT_ID"),
+            distro_image_id: key("IMAGE_ID"),
+            distro_image_version: key("IMAGE_VERSION"),
+        })
+    }
+}
+
+/// Uptime structure.
+#[derive(Debug, Clone)]
+pub struct Uptime {
+    pub formatted: String,
+    pub seconds: u32,
+    pub minutes: u32,
+    pub hours: u32,
+    pub days: u32,
+}
+
+impl Uptime {
+    /// Formats the uptime as total `hours:minutes:seconds`, for compact status bars.
+    /// For example: `72:14:05`
+    pub fn as_clock(&self) -> String {
+        format!(
+            "{:02}:{:02}:{:02}",
+            self.seconds / 3600,
+            self.seconds % 3600 / 60,
+            self.seconds % 60
+        )
+    }
+}
+
+/// Upstream kernel version, ordered by major, then minor, then patch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct KernelVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl KernelVersion {
+    pub fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+/// User account from the password database.
+#[derive(Debug, Clone)]
+pub struct UserInfo {
+    pub name: String,
+    pub home: PathBuf,
+    /// Path to the login shell, for example `/bin/bash`.
+    pub shell: String,
+    pub uid: u32,
+    pub gid: u32,
+}
+
+/// Type of information to obtain.
+#[derive(PartialEq)]
+pub enum Type {
+    Username,
+    HostName,
+    KernelVersion,
+    /// Build string of the kernel, for example `#1 SMP PREEMPT_DYNAMIC Tue, 14 Feb 2023 22:08:08`.
+    KernelBuild,
+}
+
+/// Error returned when parsing an unknown `Type` name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseTypeError(pub String);
+
+impl fmt::Display for ParseTypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "unknown field `{}`, expected `username`, `hostname`, `kernel` or `kernel-build`",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for ParseTypeError {}
+
+impl FromStr for Type {
+    type Err = ParseTypeError;
+
+    /// Parses a field name, such as `username`, into its `Type`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "username" => Ok(Self::Username),
+            "hostname" => Ok(Self::HostName),
+            "kernel" => Ok(Self::KernelVersion),
+            "kernel-build" => Ok(Self::KernelBuild),
+            _ => Err(ParseTypeError(s.to_owned())),
+        }
+    }
+}
+
+/// Enables or disables printing internal diagnostics to stderr, such as files and commands that
+/// failed.
+/// Diagnostics are disabled by default, failures are only reported through the return values.
+pub fn set_logging(enabled: bool) {
+    LOGGING.store(enabled, Ordering::Relaxed);
+}
+
+/// Errors that can occur while fetching system information.
+#[derive(Debug)]
+pub enum InfoError {
+    /// A required file couldn't be read.
+    Read {
+        path: &'static str,
+        source: io::Error,
+    },
+    /// A required value was missing or couldn't be parsed.
+    Missing(&'static str),
+    /// The current platform isn't supported.
+    Unsupported,
+    /// `/proc` isn't mounted, which is common in minimal chroots.
+    ProcNotMounted,
+    /// The async fetch was cancelled before it finished, because the tokio runtime shut down.
+    #[cfg(feature = "async")]
+    Cancelled,
+}
+
+impl fmt::Display for InfoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Read { path, source } => write!(f, "failed reading {path}: {source}"),
+            Self::Missing(name) => write!(f, "failed getting the {name}"),
+            Self::Unsupported => write!(f, "the current platform isn't supported"),
+            Self::ProcNotMounted => write!(f, "/proc isn't mounted, run with /proc mounted"),
+            #[cfg(feature = "async")]
+            Self::Cancelled => write!(f, "the fetch was cancelled"),
+        }
+    }
+}
+
+impl std::error::Error for InfoError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Read { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Builds the error for a failed read of a required file.
+/// A missing file under `/proc` is reported as `ProcNotMounted` when `/proc` itself is empty.
+fn read_error(path: &'static str, source: io::Error) -> InfoError {
+    log!("Failed reading {path}: {source}");
+    if path.starts_with("/proc/")
+        && source.kind() == io::ErrorKind::NotFound
+        && !Path::new("/proc/self").exists()
+    {
+        return InfoError::ProcNotMounted;
+    }
+
+    InfoError::Read { path, source }
+}
+
+/// Reads a file that the fetch can't do without.
+fn read_required(path: &'static str) -> Result<String, InfoError> {
+    read_to_string(path).map_err(|source| read_error(path, source))
+}
+
+/// Strips ANSI escape sequences and control characters from `s`, so that it can safely be printed
+/// to a terminal.
+pub fn sanitize(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(char) = chars.next() {
+        if char != '\x1b' {
+            if !char.is_control() {
+                result.push(char)
+            }
+
+            continue;
+        }
+
+        match chars.next() {
+            // CSI sequences, such as colors, end with a byte in the `@` to `~` range.
+            Some('[') => {
+                for char in chars.by_ref() {
+                    if ('@'..='~').contains(&char) {
+                        break;
+                    }
+                }
+            }
+            // OSC sequences, such as window titles, end with BEL or `ESC \`.
+            Some(']') => {
+                while let Some(char) = chars.next() {
+                    if char == '\x07' {
+                        break;
+                    }
+
+                    if char == '\x1b' {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            // Everything else is a two-character escape.
+            _ => (),
+        }
+    }
+
+    result
+}
+
+/// Parses the given os-release key as a `String`.
+fn parse_osr_key(os_release: &str, key: &str) -> Option<String> {
+    parse_key_value_file(os_release, key)
+}
+
+/// Parses the given os-release key, borrowing it from `os_release` unless it has to be unescaped
+/// or spans multiple lines.
+fn parse_osr_key_ref<'a>(os_release: &'a str, key: &str) -> Option<Cow<'a, str>> {
+    let prefix = format!("{key}=");
+    let value = os_release
+        .lines()
+        .find_map(|line| line.trim_start().strip_prefix(&prefix))?;
+    let quote = match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => quote,
+        _ => return Some(Cow::Borrowed(value.trim_end())),
+    };
+
+    match value[1..].find(quote) {
+        Some(end) if quote == '\'' || !value[1..=end].contains('\\') => {
+            Some(Cow::Borrowed(&value[1..=end]))
+        }
+        _ => parse_key_value_file(os_release, key).map(Cow::Owned),
+    }
+}
+
+/// Parses the given key out of a shell-like `KEY=value` file, such as `/etc/os-release`,
+/// `/etc/lsb-release` or `~/.config/user-dirs.dirs`.
+/// Surrounding quotes are removed, and quoted values may span multiple lines.
+pub fn parse_key_value_file(contents: &str, key: &str) -> Option<String> {
+    let prefix = format!("{key}=");
+    let mut lines = contents.lines();
+    let value = loop {
+        if let Some(value) = lines.next()?.trim_start().strip_prefix(&prefix) {
+            break value;
+        }
+    };
+
+    let quote = match value.chars().next() {
+        Some(quote @ ('"' | '\'')) => quote,
+        _ => return Some(value.trim_end().to_owned()),
+    };
+
+    let mut result = String::new();
+    let mut line = &value[1..];
+    loop {
+        let mut chars = line.chars();
+        while let Some(char) = chars.next() {
+            if char == quote {
+                return Some(result);
+            }
+
+            // Double-quoted values may escape characters, such as `\"`.
+            if char == '\\' && quote == '"' {
+                if let Some(escaped) = chars.next() {
+                    result.push(escaped);
+                }
+
+                continue;
+            }
+
+            result.push(char);
+        }
+
+        // The closing quote is on a later line.
+        match lines.next() {
+            Some(next) => {
+                result.push('\n');
+                line = next;
+            }
+            // The closing quote is missing, so keep what has been read.
+            None => return Some(result),
+        }
+    }
+}
+
+/// Parses the given cpuinfo key of the first CPU as a `String`.
+fn parse_cpuinfo_key(cpuinfo: &str, key: &str) -> Option<String> {
+    cpuinfo.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        (name.trim() == key).then(|| value.trim().to_owned())
+    })
+}
+
+/// Parses the given cpuinfo key of the first CPU as a number.
+fn parse_cpuinfo_num<T: FromStr>(cpuinfo: &str, key: &str) -> Option<T> {
+    parse_cpuinfo_key(cpuinfo, key)?.parse().ok()
+}
+
+/// Parses every MemInfo key in a single pass, mapping them to their value.
+/// Most values are in kilobytes, except for counts such as `HugePages_Total`.
+fn parse_meminfo(meminfo: &str) -> HashMap<&str, u64> {
+    meminfo_lines(meminfo)
+        .map(|(key, value, _)| (key, value))
+        .collect()
+}
+
+/// Splits the MemInfo lines into their key, value and unit, such as `kB`.
+fn meminfo_lines(meminfo: &str) -> impl Iterator<Item = (&str, u64, Option<&str>)> {
+    meminfo.lines().filter_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        let mut parts = value.split_whitespace();
+        Some((key, parts.next()?.parse().ok()?, parts.next()))
+    })
+}
+
+/// Parses every MemInfo key, with `kB` values converted to bytes.
+fn parse_meminfo_raw(meminfo: &str) -> HashMap<String, u64> {
+    meminfo_lines(meminfo)
+        .map(|(key, value, unit)| {
+            let scale = if unit == Some("kB") { 1024 } else { 1 };
+            (key.to_owned(), value * scale)
+        })
+        .collect()
+}
+
+/// Builds the memory information out of the parsed MemInfo keys.
+fn parse_memory(meminfo: &HashMap<&str, u64>, config: &Config) -> Result<Memory, InfoError> {
+    let kb = |key| meminfo.get(key).copied().ok_or(InfoError::Missing(key));
+    let total_kb = kb("MemTotal")?;
+    let cached_kb = kb("Cached")?;
+    let reclaimable_kb = kb("SReclaimable").ok();
+    let mut available_kb = kb("MemAvailable")?;
+    if config.available_includes_reclaimable {
+        available_kb = (available_kb + reclaimable_kb.unwrap_or_default()).min(total_kb);
+    }
+    let used_kb = total_kb.saturating_sub(available_kb);
+
+    Ok(Memory {
+        total_mem: config.format_kb(total_kb as f64),
+        cached_mem: config.format_kb(cached_kb as f64),
+        available_mem: config.format_kb(available_kb as f64),
+        used_mem: config.format_kb(used_kb as f64),
+        total_bytes: total_kb * 1024,
+        cached_bytes: cached_kb * 1024,
+        available_bytes: available_kb * 1024,
+        used_bytes: used_kb * 1024,
+        swap_total_bytes: kb("SwapTotal").unwrap_or_default() * 1024,
+        swap_free_bytes: kb("SwapFree").unwrap_or_default() * 1024,
+        reclaimable_mem: reclaimable_kb
+            .map(|reclaimable_kb| config.format_kb(reclaimable_kb as f64)),
+        reclaimable_bytes: reclaimable_kb.map(|reclaimable_kb| reclaimable_kb * 1024),
+    })
+}
+
+/// Fetches the memory information on its own, reading `/proc/meminfo` once.
+/// This is much cheaper than `get_system_information` for tools that poll memory frequently.
+pub fn get_memory() -> Option<Memory> {
+    let meminfo = read_to_string("/proc/meminfo")
+        .map_err(|error| log!("Failed reading /proc/meminfo: {error}"))
+        .ok()?;
+    parse_memory(&parse_meminfo(&meminfo), &Config::default()).ok()
+}
+
+/// Reads every `/proc/meminfo` key, with `kB` values converted to bytes.
+/// Counts without a unit, such as `HugePages_Total`, are kept as they are.
+pub fn meminfo_raw() -> Option<HashMap<String, u64>> {
+    let meminfo = read_to_string("/proc/meminfo")
+        .map_err(|error| log!("Failed reading /proc/meminfo: {error}"))
+        .ok()?;
+    Some(parse_meminfo_raw(&meminfo))
+}
+
+/// Converts bytes to gigabytes.
+fn bytes_to_gb(bytes: u64) -> String {
+    kb_to_gb(bytes as f64 / 1000.0)
+}
+
+/// Converts kilobytes to gigabytes.
+fn kb_to_gb(number: f64) -> String {
+    let (value, unit) = kb_to_parts(number, MemUnit::Decimal);
+    format!("{value:.2} {unit}")
+}
+
+/// Converts kilobytes to gigabytes in the given unit system, returning the value and the unit
+/// separately.
+/// For example: `(15.62, "GB")`
+#[cfg(feature = "byte-unit")]
+pub fn kb_to_parts(kb: f64, unit_system: MemUnit) -> (f64, &'static str) {
+    // `/proc/meminfo` reports KiB, labelled kB, so binary units convert from KiB.
+    let (input, unit) = match unit_system {
+        MemUnit::Decimal => (byte_unit::ByteUnit::KB, byte_unit::ByteUnit::GB),
+        MemUnit::Binary => (byte_unit::ByteUnit::KiB, byte_unit::ByteUnit::GiB),
+    };
+    // `byte_unit` refuses negative values, which can't be represented in bytes anyway.
+    let adjusted = Byte::from_unit(kb.max(0.0), input)
+        .unwrap_or_default()
+        .get_adjusted_unit(unit);
+    (adjusted.get_value(), unit.as_str())
+}
+
+/// Converts kilobytes to gigabytes in the given unit system, returning the value and the unit
+/// separately.
+/// For example: `(15.62, "GB")`
+#[cfg(not(feature = "byte-unit"))]
+pub fn kb_to_parts(kb: f64, unit_system: MemUnit) -> (f64, &'static str) {
+    // Clamped like the `byte_unit` implementation, which refuses negative values.
+    let kb = kb.max(0.0);
+    match unit_system {
+        MemUnit::Decimal => (kb / 1_000_000.0, "GB"),
+        MemUnit::Binary => (kb / 1_048_576.0, "GiB"),
+    }
+}
+
+/// Returns the entries in `dir` whose names start with `prefix`, sorted by name.
+fn dir_entries_with_prefix(dir: impl AsRef<Path>, prefix: &str) -> Vec<PathBuf> {
+    let Ok(entries) = read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut paths = entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with(prefix))
+        .map(|entry| entry.path())
+        .collect::<Vec<_>>();
+    paths.sort();
+    paths
+}
+
+/// Returns the `cpuN` directories in sysfs, ordered by CPU number.
+fn cpu_dirs() -> Vec<PathBuf> {
+    let mut cpus = dir_entries_with_prefix("/sys/devices/system/cpu", "cpu")
+        .into_iter()
+        .filter_map(|cpu| {
+            let number: u32 = cpu
+                .file_name()?
+                .to_str()?
+                .strip_prefix("cpu")?
+                .parse()
+                .ok()?;
+            Some((number, cpu))
+        })
+        .collect::<Vec<_>>();
+    cpus.sort_unstable_by_key(|(number, _)| *number);
+    cpus.into_iter().map(|(_, cpu)| cpu).collect()
+}
+
+/// Returns the `hwmon*` directories of a device.
+fn hwmon_dirs(device: impl AsRef<Path>) -> Vec<PathBuf> {
+    dir_entries_with_prefix(device.as_ref().join("hwmon"), "hwmon")
+}
+
+/// Reads a hwmon `temp*_input` file, converting it from millidegrees to degrees Celsius.
+fn read_hwmon_temp(path: impl AsRef<Path>) -> Option<f32> {
+    let millidegrees: f32 = read_to_string(path).ok()?.trim().parse().ok()?;
+    Some(millidegrees / 1000.0)
+}
+
+/// Returns the DRM card directories, such as `/sys/class/drm/card0`.
+/// Connectors like `card0-DP-1` are skipped.
+fn drm_cards() -> Vec<PathBuf> {
+    dir_entries_with_prefix("/sys/class/drm", "card")
+        .into_iter()
+        .filter(|card| {
+            !card
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .contains('-')
+        })
+        .collect()
+}
+
+/// Detects the GPU that is driving the display, which matters on hybrid setups like
+/// Optimus and PRIME.
+fn get_primary_gpu() -> Option<String> {
+    let cards = drm_cards();
+    // On hybrid setups the secondary GPU is runtime-suspended while it isn't in use.
+    let active = cards
+        .iter()
+        .filter(|card| {
+            read_sysfs(card.join("device/power/runtime_status")).as_deref() == Some("active")
+        })
+        .collect::<Vec<_>>();
+    let card = if active.len() == 1 {
+        active[0]
+    } else {
+        cards
+            .iter()
+            .find(|card| read_sysfs(card.join("device/boot_vga")).as_deref() == Some("1"))?
+    };
+
+    let name = card.file_name()?.to_string_lossy();
+    let driver = std::fs::read_link(card.join("device/driver")).ok();
+    Some(match driver.as_deref().and_then(Path::file_name) {
+        Some(driver) => format!("{name} ({})", driver.to_string_lossy()),
+        None => name.into_owned(),
+    })
+}
+
+/// Returns the contents of every non-empty EDID exposed by the DRM connectors.
+fn drm_edids() -> Vec<Vec<u8>> {
+    dir_entries_with_prefix("/sys/class/drm", "card")
+        .into_iter()
+        .filter_map(|connector| std::fs::read(connector.join("edid")).ok())
+        // EDIDs are made up of 128-byte blocks, disconnected connectors expose an empty file.
+        .filter(|edid| edid.len() >= 128)
+        .collect()
+}
+
+/// Parses the preferred mode out of an EDID's first detailed timing descriptor.
+/// Returns the resolution in pixels and the image size in millimeters, as `(width, height)`.
+fn parse_edid_preferred_mode(edid: &[u8]) -> Option<((u32, u32), (u32, u32))> {
+    let descriptor = edid.get(54..72)?;
+    // A pixel clock of zero means that the descriptor isn't a timing descriptor.
+    if descriptor[0] == 0 && descriptor[1] == 0 {
+        return None;
+    }
+
+    let high_nibble = |low: u8, high: u8| u32::from(low) | u32::from(high >> 4) << 8;
+    let low_nibble = |low: u8, high: u8| u32::from(low) | u32::from(high & 0x0f) << 8;
+    let resolution = (
+        high_nibble(descriptor[2], descriptor[4]),
+        high_nibble(descriptor[5], descriptor[7]),
+    );
+    let size_mm = (
+        high_nibble(descriptor[12], descriptor[14]),
+        low_nibble(descriptor[13], descriptor[14]),
+    );
+    Some((resolution, size_mm))
+}
+
+/// Parses the monitor information out of an EDID.
+fn parse_edid(edid: &[u8]) -> Option<MonitorInfo> {
+    const HEADER: [u8; 8] = [0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00];
+    if edid.len() < 128 || edid[..8] != HEADER {
+        return None;
+    }
+
+    // Three 5-bit letters, where 1 is `A`.
+    let pnp_id = u16::from_be_bytes([edid[8], edid[9]]);
+    let manufacturer = [10, 5, 0]
+        .iter()
+        .map(|shift| (b'A' - 1 + (pnp_id >> shift & 0x1F) as u8) as char)
+        .collect::<String>();
+    let product_code = u16::from_le_bytes([edid[10], edid[11]]);
+    let serial_number = u32::from_le_bytes(edid[12..16].try_into().ok()?);
+
+    // Display descriptors have a zero pixel clock, followed by their tag and up to 13 characters of
+    // text, terminated by a newline.
+    let descriptor_text = |tag: u8| {
+        edid[54..126].chunks_exact(18).find_map(|descriptor| {
+            if descriptor[..2] != [0, 0] || descriptor[3] != tag {
+                return None;
+            }
+
+            let text = String::from_utf8_lossy(&descriptor[5..]);
+            let text = text.split('\n').next()?.trim();
+            (!text.is_empty()).then(|| text.to_owned())
+        })
+    };
+    let model = descriptor_text(0xFC).unwrap_or_else(|| format!("{product_code:04X}"));
+    let serial =
+        descriptor_text(0xFF).or_else(|| (serial_number != 0).then(|| serial_number.to_string()));
+
+    // The detailed timing is in millimeters, the basic display parameters only in centimeters.
+    let size_mm = match parse_edid_preferred_mode(edid) {
+        Some((_, (width, height))) if width > 0 && height > 0 => (width as u16, height as u16),
+        _ => (u16::from(edid[21]) * 10, u16::from(edid[22]) * 10),
+    };
+
+    // Digital displays report their bits per color channel since EDID 1.4, as 6 + 2 * (n - 1).
+    // Before that, the same bits are reserved, or mean something else for analog displays.
+    let is_edid_1_4 = edid[18] == 1 && edid[19] >= 4;
+    let color_depth = match edid[20] >> 4 & 0x07 {
+        bits @ 1..=6 if is_edid_1_4 && edid[20] & 0x80 != 0 => Some(3 * (4 + 2 * bits)),
+        _ => None,
+    };
+
+    Some(MonitorInfo {
+        manufacturer,
+        model,
+        serial,
+        size_mm,
+        color_depth,
+    })
+}
+
+/// Fetches every monitor that exposes an EDID.
+/// Monitors that don't report their color depth fall back to the depth of the display server.
+fn get_monitors(config: &Config) -> Vec<MonitorInfo> {
+    let mut monitors = drm_edids()
+        .iter()
+        .filter_map(|edid| parse_edid(edid))
+        .collect::<Vec<_>>();
+    if monitors.iter().any(|monitor| monitor.color_depth.is_none()) {
+        let fallback = get_display_color_depth(config);
+        for monitor in &mut monitors {
+            monitor.color_depth = monitor.color_depth.or(fallback);
+        }
+    }
+
+    monitors
+}
+
+/// Reads the color depth of the X11 root window through `xdpyinfo`, or of the framebuffer console.
+fn get_display_color_depth(config: &Config) -> Option<u8> {
+    if std::env::var_os("DISPLAY").is_some() {
+        // For example `  depth of root window:    24 planes`.
+        let depth = command_output(config, "xdpyinfo", &[]).and_then(|xdpyinfo| {
+            xdpyinfo
+                .lines()
+                .find_map(|line| line.trim().strip_prefix("depth of root window:"))?
+                .split_whitespace()
+                .next()?
+                .parse()
+                .ok()
+        });
+        if depth.is_some() {
+            return depth;
+        }
+    }
+
+    // 32 bits per pixel padding 24 bits of color is the common case.
+    match read_sysfs("/sys/class/graphics/fb0/bits_per_pixel")?
+        .parse()
+        .ok()?
+    {
+        32 => Some(24),
+        bits => Some(bits),
+    }
+}
+
+/// Resolves the display DPI from `Xft.dpi` in `~/.Xresources`, the GTK settings, the GNOME text
+/// scaling factor, or the physical size of the first connected display.
+fn get_dpi(config: &Config) -> Option<u32> {
+    let home = std::env::var("HOME").unwrap_or_default();
+    let xresources = read_to_string(format!("{home}/.Xresources")).unwrap_or_default();
+    let xft_dpi = xresources.lines().find_map(|line| {
+        let (key, value) = line.split_once(':')?;
+        (key.trim() == "Xft.dpi").then(|| value.trim().parse::<f32>().ok())?
+    });
+    if let Some(dpi) = xft_dpi {
+        return Some(dpi.round() as u32);
+    }
+
+    // GTK stores the DPI multiplied by 1024.
+    let gtk_settings = read_to_string(format!("{home}/.config/gtk-3.0/settings.ini"))
+        .unwrap_or_default()
+        .lines()
+        .find_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "gtk-xft-dpi").then(|| value.trim().parse::<u32>().ok())?
+        });
+    if let Some(dpi) = gtk_settings {
+        return Some(dpi / 1024);
+    }
+
+    // `gsettings` answers with the schema default on any system that has glib installed, so it's
+    // only asked inside a GNOME session, and its default factor doesn't override the EDID.
+    let in_gnome = std::env::var("XDG_CURRENT_DESKTOP")
+        .is_ok_and(|desktop| desktop.contains("GNOME"))
+        && (std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some());
+    let text_scaling_factor = in_gnome
+        .then(|| {
+            command_output(
+                config,
+                "gsettings",
+                &["get", "org.gnome.desktop.interface", "text-scaling-factor"],
+            )
+        })
+        .flatten()
+        .and_then(|factor| factor.parse::<f32>().ok());
+    if let Some(factor) = text_scaling_factor.filter(|factor| *factor != 1.0) {
+        return Some((96.0 * factor).round() as u32);
+    }
+
+    drm_edids()
+        .iter()
+        .find_map(|edid| {
+            let ((width, _), (width_mm, _)) = parse_edid_preferred_mode(edid)?;
+            if width_mm == 0 {
+                return None;
+            }
+
+            Some((width as f32 / (width_mm as f32 / 25.4)).round() as u32)
+        })
+        .or_else(|| text_scaling_factor.map(|_| 96))
+}
+
+/// Returns the user's configuration directory, `$XDG_CONFIG_HOME` or `~/.config`.
+fn config_dir() -> Option<PathBuf> {
+    std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| Some(Path::new(&std::env::var_os("HOME")?).join(".config")))
+}
+
+/// Detects the terminal emulator that we're running in, through the variables it sets.
+fn detect_terminal() -> Option<&'static str> {
+    let var = |name| std::env::var(name).unwrap_or_default();
+    let term = var("TERM");
+    if std::env::var_os("KITTY_WINDOW_ID").is_some() || term == "xterm-kitty" {
+        Some("kitty")
+    } else if std::env::var_os("ALACRITTY_WINDOW_ID").is_some()
+        || std::env::var_os("ALACRITTY_SOCKET").is_some()
+        || term == "alacritty"
+    {
+        Some("alacritty")
+    } else if term.starts_with("foot") {
+        Some("foot")
+    } else {
+        None
+    }
+}
+
+/// Reads the font family from the config of the detected terminal.
+fn get_terminal_font() -> Option<String> {
+    let config_dir = config_dir()?;
+    let unquote = |value: &str| value.trim().trim_matches(['"', '\'']).to_owned();
+    match detect_terminal()? {
+        // `font_family JetBrains Mono`
+        "kitty" => read_to_string(config_dir.join("kitty/kitty.conf"))
+            .ok()?
+            .lines()
+            .find_map(|line| Some(line.trim().strip_prefix("font_family")?.trim().to_owned()))
+            .filter(|family| !family.is_empty()),
+        // `[font.normal]` followed by `family = "JetBrains Mono"`, or the inline
+        // `normal = { family = "JetBrains Mono" }` under `[font]`.
+        "alacritty" => {
+            let config = read_to_string(config_dir.join("alacritty/alacritty.toml")).ok()?;
+            let mut section = "";
+            config.lines().find_map(|line| {
+                let line = line.trim();
+                if line.starts_with('[') {
+                    section = line.trim_matches(['[', ']']);
+                    return None;
+                }
+
+                let (key, value) = line.split_once('=')?;
+                match (section, key.trim()) {
+                    ("font.normal", "family") => Some(unquote(value)),
+                    ("font", "normal") => {
+                        let family = value.split("family").nth(1)?.split_once('=')?.1;
+                        Some(unquote(family.split([',', '}']).next()?))
+                    }
+                    _ => None,
+                }
+            })
+        }
+        // `font=JetBrains Mono:size=11`, where multiple fonts are separated by commas.
+        "foot" => read_to_string(config_dir.join("foot/foot.ini"))
+            .ok()?
+            .lines()
+            .find_map(|line| {
+                let (key, value) = line.split_once('=')?;
+                (key.trim() == "font").then(|| value.split([':', ',']).next().map(unquote))?
+            }),
+        _ => None,
+    }
+}
+
+/// How long `command_output` waits for a program before killing it.
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Runs `program` with `args` and returns its trimmed stdout, if it ran successfully within
+/// `COMMAND_TIMEOUT` and `config` allows running programs.
+fn command_output(config: &Config, program: &str, args: &[&str]) -> Option<String> {
+    if !config.allow_subprocess {
+        return None;
+    }
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|error| log!("Failed running {program}: {error}"))
+        .ok()?;
+    let deadline = Instant::now() + COMMAND_TIMEOUT;
+
+    // Drain stdout on another thread so a chatty program can't block on a full pipe, and through
+    // a channel so a daemon it forked, which inherits the pipe, can't keep us waiting either.
+    let mut stdout = child.stdout.take()?;
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut output = Vec::new();
+        let _ = sender.send(stdout.read_to_end(&mut output).map(|_| output));
+    });
+
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) if Instant::now() < deadline => thread::sleep(Duration::from_millis(10)),
+            Ok(None) => {
+                log!("{program} didn't exit within {COMMAND_TIMEOUT:?}, killing it");
+                let _ = child.kill();
+                let _ = child.wait();
+                return None;
+            }
+            Err(error) => {
+                log!("Failed waiting for {program}: {error}");
+                return None;
+            }
+        }
+    };
+    if !status.success() {
+        log!("{program} exited with {status}");
+        return None;
+    }
+
+    let output = receiver
+        .recv_timeout(deadline.saturating_duration_since(Instant::now()))
+        .ok()?
+        .ok()?;
+    Some(String::from_utf8(output).ok()?.trim().to_owned())
+}
+
+/// Fetches the GPU temperature in degrees Celsius.
+/// AMD and Intel GPUs expose it through hwmon, NVIDIA GPUs are queried through `nvidia-smi`.
+fn get_gpu_temp(config: &Config) -> Option<f32> {
+    drm_cards()
+        .iter()
+        .flat_map(|card| hwmon_dirs(card.join("device")))
+        .find_map(|hwmon| read_hwmon_temp(hwmon.join("temp1_input")))
+        .or_else(|| {
+            command_output(
+                config,
+                "nvidia-smi",
+                &[
+                    "--query-gpu=temperature.gpu",
+                    "--format=csv,noheader,nounits",
+                ],
+            )?
+            .lines()
+            .next()?
+            .trim()
+            .parse()
+            .ok()
+        })
+}
+
+/// Reads the trimmed contents of a single-value file, such as a sysfs attribute.
+fn read_sysfs(path: impl AsRef<Path>) -> Option<String> {
+    Some(read_to_string(path).ok()?.trim().to_owned())
+}
+
+/// Fetches every battery under `/sys/class/power_supply`.
+fn get_batteries() -> Vec<Battery> {
+    dir_entries_with_prefix("/sys/class/power_supply", "BAT")
+        .into_iter()
+        .filter_map(|battery| {
+            // Some batteries report charge (µAh) instead of energy (µWh).
+            let read_u64 = |name: &str| read_sysfs(battery.join(name))?.parse::<u64>().ok();
+            let energy_full = read_u64("energy_full");
+            let charge_full = read_u64("charge_full");
+            // Pairs the full and design capacity of the same kind, energy or charge.
+            let read_health = |full: &str, design: &str| {
+                let full = read_sysfs(battery.join(full))?.parse::<f32>().ok()?;
+                let design = read_sysfs(battery.join(design))?.parse::<f32>().ok()?;
+                (design > 0.0).then(|| full / design * 100.0)
+            };
+            let health_percent = read_health("energy_full", "energy_full_design")
+                .or_else(|| read_health("charge_full", "charge_full_design"));
+            let percentage = match read_sysfs(battery.join("capacity")) {
+                Some(capacity) => capacity.parse().ok()?,
+                None => {
+                    let (now, full) = match energy_full {
+                        Some(full) => (read_u64("energy_now")?, full),
+                        None => (read_u64("charge_now")?, charge_full?),
+                    };
+                    now as f32 / full as f32 * 100.0
+                }
+            };
+
+            Some(Battery {
+                name: battery.file_name()?.to_string_lossy().into_owned(),
+                percentage,
+                status: read_sysfs(battery.join("status")).unwrap_or_default(),
+                energy_full,
+                charge_full,
+                health_percent,
+            })
+        })
+        .collect()
+}
+
+/// Returns the combined percentage of all `batteries`, weighted by how much each battery holds.
+/// If the batteries don't all report their energy, or all their charge, the plain average is
+/// returned instead, as µWh and µAh can't be compared.
+pub fn overall_percentage(batteries: &[Battery]) -> Option<f32> {
+    if batteries.is_empty() {
+        return None;
+    }
+
+    let capacities_of = |capacity: fn(&Battery) -> Option<u64>| {
+        batteries.iter().map(capacity).collect::<Option<Vec<_>>>()
+    };
+    let capacities = capacities_of(|battery| battery.energy_full)
+        .or_else(|| capacities_of(|battery| battery.charge_full));
+    match capacities {
+        Some(capacities) if capacities.iter().sum::<u64>() > 0 => {
+            let total = capacities.iter().sum::<u64>() as f32;
+            Some(
+                batteries
+                    .iter()
+                    .zip(capacities)
+                    .map(|(battery, capacity)| battery.percentage * capacity as f32)
+                    .sum::<f32>()
+                    / total,
+            )
+        }
+        _ => Some(
+            batteries
+                .iter()
+                .map(|battery| battery.percentage)
+                .sum::<f32>()
+                / batteries.len() as f32,
+        ),
+    }
+}
+
+/// Cumulative CPU time from the aggregate `cpu` line of `/proc/stat`, in clock ticks.
+#[derive(Clone, Copy, Default)]
+struct CpuStat {
+    user: u64,
+    nice: u64,
+    system: u64,
+    idle: u64,
+    iowait: u64,
+    irq: u64,
+    softirq: u64,
+    steal: u64,
+}
+
+impl CpuStat {
+    /// Reads the current CPU time from `/proc/stat`.
+    fn read() -> Option<Self> {
+        let stat = read_to_string("/proc/stat")
+            .map_err(|error| log!("Failed reading /proc/stat: {error}"))
+            .ok()?;
+        let mut values = stat
+            .lines()
+            .find(|line| line.starts_with("cpu "))?
+            .split_whitespace()
+            .skip(1)
+            .map(|value| value.parse().unwrap_or_default());
+        let mut next = || values.next().unwrap_or_default();
+
+        Some(Self {
+            user: next(),
+            nice: next(),
+            system: next(),
+            idle: next(),
+            iowait: next(),
+            irq: next(),
+            softirq: next(),
+            steal: next(),
+        })
+    }
+
+    /// Time spent idle, including waiting on I/O.
+    fn idle(&self) -> u64 {
+        self.idle + self.iowait
+    }
+
+    /// Time spent doing work.
+    fn busy(&self) -> u64 {
+        self.user + self.nice + self.system + self.irq + self.softirq + self.steal
+    }
+
+    /// Returns the busy percentage between `earlier` and `self`.
+    fn usage_since(&self, earlier: &CpuStat) -> f32 {
+        let busy = self.busy().saturating_sub(earlier.busy());
+        let total = busy + self.idle().saturating_sub(earlier.idle());
+        if total == 0 {
+            return 0.0;
+        }
+
+        busy as f32 / total as f32 * 100.0
+    }
+}
+
+/// Share of the CPU time spent in each state over `cpu_times`' sample, in percent.
+/// The shares add up to roughly 100%, as the kernel occasionally moves `iowait` backwards.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CpuTimes {
+    pub user: f32,
+    pub nice: f32,
+    pub system: f32,
+    pub idle: f32,
+    pub iowait: f32,
+    pub irq: f32,
+    pub softirq: f32,
+    /// Time stolen by the hypervisor for other virtual machines.
+    pub steal: f32,
+}
+
+/// Returns how the CPU time was split between user, system, I/O wait and the other states over
+/// `sample`. This blocks the calling thread for `sample`.
+pub fn cpu_times(sample: Duration) -> Option<CpuTimes> {
+    let earlier = CpuStat::read()?;
+    thread::sleep(sample);
+    let later = CpuStat::read()?;
+
+    let delta = |field: fn(&CpuStat) -> u64| field(&later).saturating_sub(field(&earlier));
+    let total = delta(|stat| stat.busy() + stat.idle());
+    if total == 0 {
+        return Some(CpuTimes::default());
+    }
+
+    let share = |field: fn(&CpuStat) -> u64| delta(field) as f32 / total as f32 * 100.0;
+    Some(CpuTimes {
+        user: share(|stat| stat.user),
+        nice: share(|stat| stat.nice),
+        system: share(|stat| stat.system),
+        idle: share(|stat| stat.idle),
+        iowait: share(|stat| stat.iowait),
+        irq: share(|stat| stat.irq),
+        softirq: share(|stat| stat.softirq),
+        steal: share(|stat| stat.steal),
+    })
+}
+
+/// Reads the time the system booted at, in seconds since the Unix epoch, from `btime` in
+/// `/proc/stat`.
+pub fn boot_epoch() -> Option<u64> {
+    read_to_string("/proc/stat")
+        .map_err(|error| log!("Failed reading /proc/stat: {error}"))
+        .ok()?
+        .lines()
+        .find_map(|line| line.strip_prefix("btime "))?
+        .trim()
+        .parse()
+        .ok()
+}
+
+/// Returns the average CPU usage in percent since the system booted, from a single `/proc/stat`
+/// read.
+/// This is the lifetime average rather than the current load, use `cpu_usage` for that.
+pub fn cpu_usage_since_boot() -> Option<f32> {
+    Some(CpuStat::read()?.usage_since(&CpuStat::default()))
+}
+
+/// Returns the CPU usage in percent over `interval`.
+/// This blocks the calling thread for `interval`.
+pub fn cpu_usage(interval: Duration) -> Option<f32> {
+    let earlier = CpuStat::read()?;
+    thread::sleep(interval);
+    Some(CpuStat::read()?.usage_since(&earlier))
+}
+
+/// Rates computed by `sample` from two readings taken an interval apart.
+#[derive(Debug, Clone)]
+pub struct SystemSample {
+    /// Time that actually passed between the readings.
+    pub elapsed: Duration,
+    /// CPU usage in percent.
+    pub cpu_usage: Option<f32>,
+    /// Bytes received per second, across every interface except loopback.
+    pub rx_bytes_per_sec: Option<f64>,
+    /// Bytes sent per second, across every interface except loopback.
+    pub tx_bytes_per_sec: Option<f64>,
+    /// Change of the used memory in bytes, negative when memory was freed.
+    pub used_mem_delta: Option<i64>,
+}
+
+/// Reads the total bytes received and sent by every interface except loopback from
+/// `/proc/net/dev`.
+fn read_net_totals() -> Option<(u64, u64)> {
+    let dev = read_to_string("/proc/net/dev")
+        .map_err(|error| log!("Failed reading /proc/net/dev: {error}"))
+        .ok()?;
+    // The first two lines are headers, the receive columns come before the transmit columns.
+    Some(
+        dev.lines()
+            .skip(2)
+            .filter_map(|line| {
+                let (name, counters) = line.split_once(':')?;
+                if name.trim() == "lo" {
+                    return None;
+                }
+
+                let counters = counters.split_whitespace().collect::<Vec<_>>();
+                Some((
+                    counters.first()?.parse::<u64>().ok()?,
+                    counters.get(8)?.parse::<u64>().ok()?,
+                ))
+            })
+            .fold((0, 0), |(rx, tx), (line_rx, line_tx)| {
+                (rx + line_rx, tx + line_tx)
+            }),
+    )
+}
+
+/// Shortest interval `sample` waits between its readings, as the counters it reads only advance
+/// every few milliseconds and shorter intervals would give meaningless rates.
+pub const MIN_SAMPLE_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Takes two readings `interval` apart and returns the CPU usage, network throughput and
+/// memory change over it. Values that couldn't be read in either reading are `None`.
+/// This blocks the calling thread for `interval`, or `MIN_SAMPLE_INTERVAL` if it's shorter.
+pub fn sample(interval: Duration) -> SystemSample {
+    let (cpu, net, memory) = (CpuStat::read(), read_net_totals(), get_memory());
+    let start = Instant::now();
+    thread::sleep(interval.max(MIN_SAMPLE_INTERVAL));
+    let (later_cpu, later_net, later_memory) = (CpuStat::read(), read_net_totals(), get_memory());
+    let elapsed = start.elapsed();
+
+    let per_sec =
+        |earlier: u64, later: u64| later.saturating_sub(earlier) as f64 / elapsed.as_secs_f64();
+    let net = net.zip(later_net);
+    SystemSample {
+        elapsed,
+        cpu_usage: cpu
+            .zip(later_cpu)
+            .map(|(cpu, later_cpu)| later_cpu.usage_since(&cpu)),
+        rx_bytes_per_sec: net.map(|((rx, _), (later_rx, _))| per_sec(rx, later_rx)),
+        tx_bytes_per_sec: net.map(|((_, tx), (_, later_tx))| per_sec(tx, later_tx)),
+        used_mem_delta: memory.zip(later_memory).map(|(memory, later_memory)| {
+            later_memory.used_bytes as i64 - memory.used_bytes as i64
+        }),
+    }
+}
+
+/// Throughput of a block device, as measured by `disk_io`.
+#[derive(Debug, Clone, Copy)]
+pub struct DiskIo {
+    /// Bytes read per second.
+    pub read_bps: u64,
+    /// Bytes written per second.
+    pub write_bps: u64,
+    /// Completed read and write operations per second.
+    pub iops: u64,
+}
+
+/// Reads the completed reads, sectors read, completed writes and sectors written of `device` from
+/// `/proc/diskstats`.
+fn read_disk_stats(device: &str) -> Option<[u64; 4]> {
+    let diskstats = read_to_string("/proc/diskstats")
+        .map_err(|error| log!("Failed reading /proc/diskstats: {error}"))
+        .ok()?;
+    // Each line starts with the major and minor numbers and the device name, followed by the
+    // counters.
+    diskstats.lines().find_map(|line| {
+        let fields = line.split_whitespace().collect::<Vec<_>>();
+        if fields.get(2) != Some(&device) {
+            return None;
+        }
+
+        let counter = |index: usize| fields.get(3 + index)?.parse::<u64>().ok();
+        Some([counter(0)?, counter(2)?, counter(4)?, counter(6)?])
+    })
+}
+
+/// Measures the throughput of `device`, such as `nvme0n1` or `sda`, over `sample`.
+/// Returns `None` for unknown devices. This blocks the calling thread for `sample`.
+pub fn disk_io(device: &str, sample: Duration) -> Option<DiskIo> {
+    let earlier = read_disk_stats(device)?;
+    let start = Instant::now();
+    thread::sleep(sample);
+    let later = read_disk_stats(device)?;
+    let seconds = start.elapsed().as_secs_f64();
+
+    // Sectors are always 512 bytes in `/proc/diskstats`.
+    let per_sec = |index: usize, scale: u64| {
+        (later[index].saturating_sub(earlier[index]) * scale) as f64 / seconds
+    };
+    Some(DiskIo {
+        read_bps: per_sec(1, 512) as u64,
+        write_bps: per_sec(3, 512) as u64,
+        iops: (per_sec(0, 1) + per_sec(2, 1)) as u64,
+    })
+}
+
+/// Resource usage of the current process, as returned by `self_usage`.
+#[derive(Debug, Clone)]
+pub struct SelfUsage {
+    /// Resident memory in kB.
+    pub rss_kb: u64,
+    /// Virtual memory in kB.
+    pub vsize_kb: u64,
+    /// CPU time spent in user and kernel mode.
+    pub cpu_time: Duration,
+    pub threads: u32,
+}
+
+/// Returns the number of clock ticks per second, the unit of the CPU times in `/proc`.
+#[cfg(unix)]
+fn clock_ticks_per_sec() -> u64 {
+    match unsafe { libc::sysconf(libc::_SC_CLK_TCK) } {
+        ticks if ticks > 0 => ticks as u64,
+        _ => 100,
+    }
+}
+
+/// Clock ticks only matter for `/proc`, which Windows doesn't have.
+#[cfg(windows)]
+fn clock_ticks_per_sec() -> u64 {
+    100
+}
+
+/// Reads the memory, CPU time and thread count of the current process from `/proc/self`.
+pub fn self_usage() -> Option<SelfUsage> {
+    let status = read_to_string("/proc/self/status")
+        .map_err(|error| log!("Failed reading /proc/self/status: {error}"))
+        .ok()?;
+    let status_num = |key: &str| -> Option<u64> {
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))?
+            .split_whitespace()
+            .next()?
+            .parse()
+            .ok()
+    };
+
+    // The process name is in parentheses and may contain spaces, so fields are counted from the
+    // closing one. `utime` and `stime` are the 14th and 15th fields.
+    let stat = read_to_string("/proc/self/stat")
+        .map_err(|error| log!("Failed reading /proc/self/stat: {error}"))
+        .ok()?;
+    let fields = stat
+        .rsplit_once(')')?
+        .1
+        .split_whitespace()
+        .collect::<Vec<_>>();
+    let ticks = fields.get(11)?.parse::<u64>().ok()? + fields.get(12)?.parse::<u64>().ok()?;
+
+    Some(SelfUsage {
+        rss_kb: status_num("VmRSS")?,
+        vsize_kb: status_num("VmSize")?,
+        cpu_time: Duration::from_secs_f64(ticks as f64 / clock_ticks_per_sec() as f64),
+        threads: status_num("Threads")? as u32,
+    })
+}
+
+/// Decodes the octal escapes that `/proc/mounts` uses for spaces and other special characters in
+/// paths.
+fn unescape_mount_path(path: &str) -> String {
+    let mut result = String::with_capacity(path.len());
+    let mut rest = path;
+    while let Some(index) = rest.find('\\') {
+        result.push_str(&rest[..index]);
+        rest = &rest[index..];
+        match rest
+            .get(1..4)
+            .and_then(|octal| u8::from_str_radix(octal, 8).ok())
+        {
+            Some(byte) => {
+                result.push(byte as char);
+                rest = &rest[4..];
+            }
+            None => {
+                result.push('\\');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// A single entry from `/proc/mounts`.
+struct Mount {
+    device: String,
+    mount_point: String,
+    fs_type: String,
+    options: Vec<String>,
+}
+
+/// Reads every entry from `/proc/mounts`.
+fn read_mounts() -> Vec<Mount> {
+    let mounts = read_to_string("/proc/mounts").unwrap_or_default();
+    mounts
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            Some(Mount {
+                device: unescape_mount_path(fields.next()?),
+                mount_point: unescape_mount_path(fields.next()?),
+                fs_type: fields.next()?.to_owned(),
+                options: fields.next()?.split(',').map(str::to_owned).collect(),
+            })
+        })
+        .collect()
+}
+
+/// Fetches the usage of every filesystem backed by a device, from `/proc/mounts`.
+fn get_disks() -> Vec<DiskUsage> {
+    read_mounts()
+        .into_iter()
+        .filter(|mount| mount.device.starts_with("/dev/"))
+        .filter_map(|mount| get_disk_usage(mount.device, mount.mount_point, mount.fs_type))
+        .collect()
+}
+
+/// Fetches the filesystem mounted at `/`.
+/// Container roots are usually reported as `overlay`.
+fn get_root_fs() -> Option<RootFs> {
+    // Later mounts on `/` hide the earlier ones, such as the initial `rootfs`.
+    let mount = read_mounts()
+        .into_iter()
+        .rfind(|mount| mount.mount_point == "/")?;
+    Some(RootFs {
+        device: mount.device,
+        fs_type: mount.fs_type,
+        mount_options: mount.options,
+    })
+}
+
+/// Fetches the usage of the filesystem mounted at `mount_point` through `statvfs`.
+// The `statvfs` field types differ between targets, so the casts aren't always no-ops.
+#[cfg(unix)]
+#[allow(clippy::unnecessary_cast)]
+fn get_disk_usage(device: String, mount_point: String, fs_type: String) -> Option<DiskUsage> {
+    let path = CString::new(mount_point.as_bytes()).ok()?;
+    let mut stat = unsafe { MaybeUninit::<libc::statvfs>::zeroed().assume_init() };
+    if unsafe { libc::statvfs(path.as_ptr(), &mut stat as *mut _) } != 0 {
+        return None;
+    }
+
+    let fragment_size = stat.f_frsize as u64;
+    let total_bytes = stat.f_blocks as u64 * fragment_size;
+    // Some FUSE and network filesystems report more free blocks or inodes than they have.
+    Some(DiskUsage {
+        device,
+        mount_point,
+        fs_type,
+        total_bytes,
+        used_bytes: total_bytes.saturating_sub(stat.f_bfree as u64 * fragment_size),
+        available_bytes: stat.f_bavail as u64 * fragment_size,
+        inodes_total: stat.f_files as u64,
+        inodes_used: (stat.f_files as u64).saturating_sub(stat.f_ffree as u64),
+        inodes_free: stat.f_ffree as u64,
+    })
+}
+
+/// `statvfs` isn't available on Windows.
+#[cfg(windows)]
+fn get_disk_usage(_device: String, _mount_point: String, _fs_type: String) -> Option<DiskUsage> {
+    None
+}
+
+/// Detects whether the system is running under WSL, and which version.
+fn get_wsl_version() -> Option<u8> {
+    let version = read_to_string("/proc/version").unwrap_or_default();
+    // WSL2 kernels are built as `microsoft-standard-WSL2`, WSL1 reports `Microsoft` instead.
+    if version.contains("WSL2") || version.contains("microsoft-standard") {
+        return Some(2);
+    }
+
+    if version.contains("Microsoft") {
+        return Some(1);
+    }
+
+    // Custom WSL2 kernels don't mention Microsoft, but the environment still gives it away.
+    if std::env::var_os("WSL_DISTRO_NAME").is_some() || Path::new("/run/WSL").exists() {
+        return Some(2);
+    }
+
+    None
+}
+
+/// Fetches the default systemd target, or the current runlevel on sysvinit.
+fn get_systemd_target(config: &Config) -> Option<String> {
+    if Path::new("/run/systemd/system").exists() {
+        return std::fs::read_link("/etc/systemd/system/default.target")
+            .ok()
+            .and_then(|target| Some(target.file_name()?.to_string_lossy().into_owned()))
+            .or_else(|| command_output(config, "systemctl", &["get-default"]));
+    }
+
+    if Path::new("/etc/inittab").exists() {
+        // `runlevel` prints the previous and the current runlevel, for example `N 5`.
+        let runlevel = command_output(config, "runlevel", &[])?;
+        return Some(format!("runlevel {}", runlevel.split_whitespace().nth(1)?));
+    }
+
+    None
+}
+
+/// Fetches the keyboard layout, for example `us`.
+/// Asks `localectl` first, then falls back to the X11 and console keymap configuration files.
+fn get_keyboard_layout(config: &Config) -> Option<String> {
+    let from_localectl = || {
+        let status = command_output(config, "localectl", &["status"])?;
+        ["X11 Layout:", "VC Keymap:"].iter().find_map(|key| {
+            let value = status
+                .lines()
+                .find_map(|line| line.trim().strip_prefix(key))?
+                .trim();
+            (!value.is_empty() && value != "n/a").then(|| value.to_owned())
+        })
+    };
+    let from_file = |path: &str, key: &str| {
+        parse_key_value_file(&std::fs::read_to_string(path).ok()?, key)
+            .filter(|value| !value.is_empty())
+    };
+
+    from_localectl()
+        .or_else(|| from_file("/etc/default/keyboard", "XKBLAYOUT"))
+        .or_else(|| from_file("/etc/vconsole.conf", "KEYMAP"))
+}
+
+/// Fetches the status of every CPU vulnerability the kernel knows about.
+fn get_cpu_mitigations() -> Vec<(String, String)> {
+    dir_entries_with_prefix("/sys/devices/system/cpu/vulnerabilities", "")
+        .into_iter()
+        .filter_map(|vulnerability| {
+            Some((
+                vulnerability.file_name()?.to_string_lossy().into_owned(),
+                read_sysfs(&vulnerability)?,
+            ))
+        })
+        .collect()
+}
+
+/// Files that package managers modify on every transaction, checked in order.
+const PACKAGE_MANAGER_DATABASES: &[&str] = &[
+    // pacman
+    "/var/lib/pacman/local",
+    // apt
+    "/var/log/dpkg.log",
+    // dnf
+    "/var/lib/dnf/history.sqlite",
+];
+
+/// Fetches when the system was last updated, from the modification time of the package manager's
+/// database.
+fn get_last_update() -> Option<SystemTime> {
+    PACKAGE_MANAGER_DATABASES
+        .iter()
+        .find_map(|database| std::fs::metadata(database).ok()?.modified().ok())
+}
+
+/// Parses the size in bytes out of a raw SMBIOS memory device (type 17) entry.
+fn parse_dmi_memory_device(raw: &[u8]) -> Option<u64> {
+    let word = |offset: usize| {
+        Some(u16::from_le_bytes(
+            raw.get(offset..offset + 2)?.try_into().ok()?,
+        ))
+    };
+    match word(0x0C)? {
+        // Empty slot, or unknown size.
+        0 | 0xFFFF => None,
+        // The size doesn't fit, so it's stored in megabytes in the extended size instead.
+        0x7FFF => {
+            let extended = u32::from_le_bytes(raw.get(0x1C..0x20)?.try_into().ok()?);
+            Some(u64::from(extended & 0x7FFF_FFFF) * 1024 * 1024)
+        }
+        // Bit 15 is set when the size is in kilobytes rather than megabytes.
+        size if size & 0x8000 != 0 => Some(u64::from(size & 0x7FFF) * 1024),
+        size => Some(u64::from(size) * 1024 * 1024),
+    }
+}
+
+/// Fetches the total installed RAM in bytes by summing the DMI memory devices, through the raw
+/// SMBIOS tables or `dmidecode`.
+/// Both require root.
+fn get_physical_mem_total(config: &Config) -> Option<u64> {
+    let devices = dir_entries_with_prefix("/sys/firmware/dmi/entries", "17-")
+        .into_iter()
+        .filter_map(|entry| std::fs::read(entry.join("raw")).ok())
+        .filter_map(|raw| parse_dmi_memory_device(&raw))
+        .collect::<Vec<_>>();
+    if !devices.is_empty() {
+        return Some(devices.iter().sum());
+    }
+
+    // `Size: 16 GB`, or `Size: No Module Installed` for empty slots.
+    let total = command_output(config, "dmidecode", &["--type", "17"])?
+        .lines()
+        .filter_map(|line| {
+            let mut size = line.trim().strip_prefix("Size:")?.split_whitespace();
+            let value: u64 = size.next()?.parse().ok()?;
+            let multiplier: u64 = match size.next()? {
+                "kB" | "KB" => 1024,
+                "MB" => 1024 * 1024,
+                "GB" => 1024 * 1024 * 1024,
+                "TB" => 1024 * 1024 * 1024 * 1024,
+                _ => return None,
+            };
+            Some(value * multiplier)
+        })
+        .sum::<u64>();
+    (total > 0).then_some(total)
+}
+
+/// Reads `name` from the current process's cgroup.
+/// `controller` selects the cgroup v1 hierarchy to read from, and is `None` for the unified cgroup
+/// v2 hierarchy.
+fn read_cgroup_file(controller: Option<&str>, name: &str) -> Option<String> {
+    let cgroups = read_to_string("/proc/self/cgroup").ok()?;
+    // Lines are `hierarchy-ID:controllers:path`, cgroup v2 has no controllers listed.
+    let path = cgroups.lines().find_map(|line| {
+        let mut fields = line.splitn(3, ':');
+        let (_, controllers, path) = (fields.next()?, fields.next()?, fields.next()?);
+        let matches = match controller {
+            Some(controller) => controllers.split(',').any(|name| name == controller),
+            None => controllers.is_empty(),
+        };
+        matches.then_some(path)
+    })?;
+
+    let base = match controller {
+        Some(controller) => Path::new("/sys/fs/cgroup").join(controller),
+        None => PathBuf::from("/sys/fs/cgroup"),
+    };
+    // Inside containers the cgroup is usually mounted as the root, so the host path doesn't exist.
+    read_sysfs(base.join(path.trim_start_matches('/')).join(name))
+        .or_else(|| read_sysfs(base.join(name)))
+}
+
+/// Fetches the CPU budget of the current cgroup, from `cpu.max` on cgroup v2 or the CFS quota on
+/// cgroup v1.
+fn get_cpu_quota() -> Option<f32> {
+    let (quota, period) = match read_cgroup_file(None, "cpu.max") {
+        // `max 100000` when unconstrained.
+        Some(cpu_max) => {
+            let (quota, period) = cpu_max.split_once(' ')?;
+            (quota.parse::<f32>().ok()?, period.parse::<f32>().ok()?)
+        }
+        // A quota of `-1` means unconstrained.
+        None => (
+            read_cgroup_file(Some("cpu"), "cpu.cfs_quota_us")?
+                .parse::<f32>()
+                .ok()?,
+            read_cgroup_file(Some("cpu"), "cpu.cfs_period_us")?
+                .parse::<f32>()
+                .ok()?,
+        ),
+    };
+
+    (quota > 0.0 && period > 0.0).then(|| quota / period)
+}
+
+/// Fetches the memory limit of the current cgroup in bytes, from `memory.max` on cgroup v2 or
+/// `memory.limit_in_bytes` on cgroup v1.
+fn get_mem_limit() -> Option<u64> {
+    // cgroup v2 reports `max` when unlimited, which fails to parse.
+    let limit: u64 = read_cgroup_file(None, "memory.max")
+        .or_else(|| read_cgroup_file(Some("memory"), "memory.limit_in_bytes"))?
+        .parse()
+        .ok()?;
+    // cgroup v1 reports the largest page-aligned `i64` instead.
+    (limit < 1 << 62).then_some(limit)
+}
+
+/// Detects whether SMT (hyperthreading) is active, falling back to comparing the logical and
+/// physical core counts in cpuinfo.
+fn get_smt_enabled(cpuinfo: &str) -> Option<bool> {
+    match read_sysfs("/sys/devices/system/cpu/smt/active").as_deref() {
+        Some("1") => Some(true),
+        Some("0") => Some(false),
+        _ => {
+            let siblings: u32 = parse_cpuinfo_num(cpuinfo, "siblings")?;
+            let cores: u32 = parse_cpuinfo_num(cpuinfo, "cpu cores")?;
+            Some(siblings > cores)
+        }
+    }
+}
+
+/// Detects the form factor of the machine from its SMBIOS chassis type.
+/// Falls back to guessing a laptop when the chassis type is unknown and a battery is present.
+fn get_chassis_type(cpuinfo: &str, has_battery: bool) -> Option<ChassisType> {
+    // Hypervisors usually report a desktop or unknown chassis, so the CPU flag is checked first.
+    if parse_cpuinfo_key(cpuinfo, "flags")
+        .is_some_and(|flags| flags.split_whitespace().any(|flag| flag == "hypervisor"))
+    {
+        return Some(ChassisType::Vm);
+    }
+
+    // Codes as listed in the SMBIOS specification, 1 and 2 are "Other" and "Unknown".
+    match read_sysfs("/sys/class/dmi/id/chassis_type").and_then(|code| code.parse::<u8>().ok()) {
+        Some(3..=7 | 13 | 15 | 16 | 24 | 35 | 36) => Some(ChassisType::Desktop),
+        Some(8..=10 | 14 | 31 | 32) => Some(ChassisType::Laptop),
+        Some(11 | 30) => Some(ChassisType::Tablet),
+        Some(17 | 23 | 25 | 28 | 29) => Some(ChassisType::Server),
+        Some(code) if code > 2 => Some(ChassisType::Other),
+        _ => has_battery.then_some(ChassisType::Laptop),
+    }
+}
+
+/// Fetches the active power profile, for example `balanced`, `performance` or `power-saver`.
+/// Asks power-profiles-daemon first, then falls back to the ACPI platform profile.
+fn get_power_profile(config: &Config) -> Option<String> {
+    command_output(config, "powerprofilesctl", &["get"])
+        .filter(|profile| !profile.is_empty())
+        .or_else(|| read_sysfs("/sys/firmware/acpi/platform_profile"))
+}
+
+/// Fetches the NUMA node count and the memory of each node in bytes, ordered by node number.
+/// Systems without NUMA support are reported as a single node without per-node memory.
+fn get_numa_nodes() -> (u32, Vec<u64>) {
+    let mut nodes = dir_entries_with_prefix("/sys/devices/system/node", "node")
+        .into_iter()
+        .filter_map(|node| {
+            let number: u32 = node
+                .file_name()?
+                .to_str()?
+                .strip_prefix("node")?
+                .parse()
+                .ok()?;
+            // Lines look like `Node 0 MemTotal:       16314780 kB`.
+            let mem_total_kb: u64 = read_to_string(node.join("meminfo"))
+                .ok()?
+                .lines()
+                .find_map(|line| line.split_once("MemTotal:"))?
+                .1
+                .split_whitespace()
+                .next()?
+                .parse()
+                .ok()?;
+            Some((number, mem_total_kb * 1024))
+        })
+        .collect::<Vec<_>>();
+    if nodes.is_empty() {
+        return (1, Vec::new());
+    }
+
+    nodes.sort_unstable_by_key(|(number, _)| *number);
+    (
+        nodes.len() as u32,
+        nodes.into_iter().map(|(_, mem)| mem).collect(),
+    )
+}
+
+/// Parses a kernel CPU list, such as `0-3,8,10-11`, into the CPU numbers it contains.
+fn parse_cpu_list(list: &str) -> Vec<u32> {
+    list.trim()
+        .split(',')
+        .filter_map(|range| match range.split_once('-') {
+            Some((start, end)) => Some(start.parse().ok()?..=end.parse().ok()?),
+            None => {
+                let cpu = range.parse().ok()?;
+                Some(cpu..=cpu)
+            }
+        })
+        .flatten()
+        .collect()
+}
+
+/// Counts the performance and efficiency CPUs of hybrid CPUs, as logical CPUs.
+/// Intel's hybrid CPUs expose a separate PMU per core type, other hybrid CPUs (such as ARM's
+/// big.LITTLE) are split by their relative capacity. Non-hybrid CPUs are reported as `(0, 0)`.
+fn get_hybrid_core_counts() -> (u32, u32) {
+    let (pcores, ecores) = (
+        count_cpu_list("/sys/devices/cpu_core/cpus"),
+        count_cpu_list("/sys/devices/cpu_atom/cpus"),
+    );
+    if pcores > 0 && ecores > 0 {
+        return (pcores, ecores);
+    }
+
+    let capacities = cpu_dirs()
+        .into_iter()
+        .filter_map(|cpu| read_sysfs(cpu.join("cpu_capacity"))?.parse::<u32>().ok())
+        .collect::<Vec<_>>();
+    let Some(max) = capacities.iter().max() else {
+        return (0, 0);
+    };
+
+    let pcores = capacities
+        .iter()
+        .filter(|capacity| *capacity == max)
+        .count() as u32;
+    match capacities.len() as u32 - pcores {
+        0 => (0, 0),
+        ecores => (pcores, ecores),
+    }
+}
+
+/// Reads the 1, 5 and 15 minute load averages divided by the logical CPU count from `cpuinfo`.
+/// Returns zeroes when either is unknown.
+fn get_load_per_core(cpuinfo: &str) -> (f32, f32, f32) {
+    let cpus = cpuinfo
+        .lines()
+        .filter(|line| line.starts_with("processor"))
+        .count() as f32;
+    let loads = read_sysfs("/proc/loadavg")
+        .map(|loadavg| {
+            loadavg
+                .split_whitespace()
+                .take(3)
+                .map(|load| load.parse::<f32>().unwrap_or_default())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    match loads[..] {
+        [one, five, fifteen] if cpus > 0.0 => (one / cpus, five / cpus, fifteen / cpus),
+        _ => (0.0, 0.0, 0.0),
+    }
+}
+
+/// Reads the current frequency of every CPU in MHz, ordered by CPU number.
+fn get_cpu_freqs() -> Vec<f32> {
+    cpu_dirs()
+        .into_iter()
+        .filter_map(|cpu| {
+            let khz: f32 = read_sysfs(cpu.join("cpufreq/scaling_cur_freq"))?
+                .parse()
+                .ok()?;
+            Some(khz / 1000.0)
+        })
+        .collect()
+}
+
+/// Parses a `systemd-analyze` duration, such as `1min 2.345s` or `850ms`.
+fn parse_systemd_duration(duration: &str) -> Option<Duration> {
+    duration
+        .split_whitespace()
+        .try_fold(Duration::ZERO, |total, part| {
+            let unit_start = part.find(|char: char| char.is_ascii_alphabetic())?;
+            let (value, unit) = part.split_at(unit_start);
+            let value: f64 = value.parse().ok()?;
+            let seconds = match unit {
+                "us" | "µs" => value / 1_000_000.0,
+                "ms" => value / 1000.0,
+                "s" => value,
+                "min" => value * 60.0,
+                "h" => value * 3600.0,
+                _ => return None,
+            };
+            Some(total + Duration::from_secs_f64(seconds))
+        })
+}
+
+/// Fetches how long each boot stage took through `systemd-analyze`.
+/// The first line of its output looks like
+/// `Startup finished in 3.1s (firmware) + 2.2s (loader) + 1.3s (kernel) + 4.4s (userspace) = 11s`
+fn get_boot_timing(config: &Config) -> Option<BootTiming> {
+    if !Path::new("/run/systemd/system").exists() {
+        return None;
+    }
+
+    let output = command_output(config, "systemd-analyze", &[])?;
+    let (stages, total) = output
+        .lines()
+        .next()?
+        .strip_prefix("Startup finished in ")?
+        .split_once(" = ")?;
+    let mut timing = BootTiming {
+        firmware: None,
+        loader: None,
+        kernel: None,
+        initrd: None,
+        userspace: None,
+        total: parse_systemd_duration(total)?,
+    };
+    for stage in stages.split(" + ") {
+        let (duration, name) = stage.trim_end_matches(')').rsplit_once(" (")?;
+        let slot = match name {
+            "firmware" => &mut timing.firmware,
+            "loader" => &mut timing.loader,
+            "kernel" => &mut timing.kernel,
+            "initrd" => &mut timing.initrd,
+            "userspace" => &mut timing.userspace,
+            _ => continue,
+        };
+        *slot = parse_systemd_duration(duration);
+    }
+
+    Some(timing)
+}
+
+/// Lists the idle states (C-states) of the first CPU, for example `POLL`, `C1` and `C6`.
+fn get_cpu_cstates() -> Vec<String> {
+    dir_entries_with_prefix("/sys/devices/system/cpu/cpu0/cpuidle", "state")
+        .into_iter()
+        .filter_map(|state| read_sysfs(state.join("name")))
+        .collect()
+}
+
+/// Reads the transparent hugepage mode, for example `madvise`.
+/// The file lists every mode with the active one in brackets, such as `always [madvise] never`.
+fn get_thp() -> Option<String> {
+    let modes = read_sysfs("/sys/kernel/mm/transparent_hugepage/enabled")?;
+    let (_, active) = modes.split_once('[')?;
+    Some(active.split_once(']')?.0.to_owned())
+}
+
+/// Counts the CPUs in a sysfs CPU list file, such as `/sys/devices/system/cpu/online`.
+fn count_cpu_list(path: &str) -> u32 {
+    read_sysfs(path).map_or(0, |list| parse_cpu_list(&list).len() as u32)
+}
+
+/// Picks the preemption model, such as `PREEMPT_RT` or `PREEMPT_DYNAMIC`, out of the kernel build
+/// string.
+fn parse_kernel_preempt(build: &str) -> Option<String> {
+    build
+        .split_whitespace()
+        .find(|token| token.starts_with("PREEMPT"))
+        .map(str::to_owned)
+}
+
+/// Lists the block devices in `/sys/block`, skipping loop and RAM disks.
+fn get_block_devices() -> Vec<BlockDevice> {
+    dir_entries_with_prefix("/sys/block", "")
+        .into_iter()
+        .filter_map(|device| {
+            let name = device.file_name()?.to_string_lossy().into_owned();
+            if name.starts_with("loop") || name.starts_with("ram") || name.starts_with("zram") {
+                return None;
+            }
+
+            // The size is always in 512-byte sectors, regardless of the sector size of the device.
+            let sectors: u64 = read_sysfs(device.join("size"))?.parse().ok()?;
+            Some(BlockDevice {
+                name,
+                size_bytes: sectors * 512,
+                model: read_sysfs(device.join("device/model")).filter(|model| !model.is_empty()),
+                rotational: read_sysfs(device.join("queue/rotational")).as_deref() == Some("1"),
+            })
+        })
+        .collect()
+}
+
+/// Finds the gateway of the default route, preferring IPv4 over IPv6.
+fn get_default_gateway() -> Option<IpAddr> {
+    // The addresses in `/proc/net/route` are in host byte order.
+    let ipv4 = read_to_string("/proc/net/route").ok().and_then(|routes| {
+        routes.lines().skip(1).find_map(|route| {
+            let fields = route.split_whitespace().collect::<Vec<_>>();
+            let gateway = u32::from_str_radix(fields.get(2)?, 16).ok()?;
+            (fields.get(1) == Some(&"00000000") && gateway != 0)
+                .then(|| IpAddr::V4(Ipv4Addr::from(gateway.to_ne_bytes())))
+        })
+    });
+    if ipv4.is_some() {
+        return ipv4;
+    }
+
+    // `/proc/net/ipv6_route` lists the destination, its prefix length, the source, its prefix
+    // length and then the next hop, all in network byte order.
+    read_to_string("/proc/net/ipv6_route")
+        .ok()?
+        .lines()
+        .find_map(|route| {
+            let fields = route.split_whitespace().collect::<Vec<_>>();
+            let destination = u128::from_str_radix(fields.first()?, 16).ok()?;
+            let next_hop = u128::from_str_radix(fields.get(4)?, 16).ok()?;
+            (destination == 0 && fields.get(1) == Some(&"00") && next_hop != 0)
+                .then(|| IpAddr::V6(Ipv6Addr::from(next_hop)))
+        })
+}
+
+/// Reads the temperature of every CPU core from the coretemp hwmon, labeled like `Core 0`.
+fn get_cpu_core_temps() -> Vec<(String, f32)> {
+    let mut temps = dir_entries_with_prefix("/sys/class/hwmon", "hwmon")
+        .into_iter()
+        .filter(|hwmon| read_sysfs(hwmon.join("name")).as_deref() == Some("coretemp"))
+        .flat_map(|hwmon| dir_entries_with_prefix(hwmon, "temp"))
+        .filter_map(|label| {
+            let label_name = label.file_name()?.to_str()?;
+            let input =
+                label.with_file_name(label_name.strip_suffix("_label")?.to_owned() + "_input");
+            // The package temperature is labeled `Package id 0` and skipped.
+            let label = read_sysfs(&label).filter(|label| label.starts_with("Core "))?;
+            Some((label, read_hwmon_temp(input)?))
+        })
+        .collect::<Vec<_>>();
+    // `temp10` sorts before `temp2`, so the cores are put back in order by their number.
+    temps.sort_by_key(|(label, _)| label[5..].parse::<u32>().unwrap_or_default());
+    temps
+}
+
+/// Fetches the GPU utilization in percent.
+/// AMD GPUs expose it through sysfs, NVIDIA GPUs are queried through `nvidia-smi`. Intel GPUs
+/// don't report it.
+fn get_gpu_usage(config: &Config) -> Option<f32> {
+    drm_cards()
+        .iter()
+        .find_map(|card| {
+            read_sysfs(card.join("device/gpu_busy_percent"))?
+                .parse()
+                .ok()
+        })
+        .or_else(|| {
+            command_output(
+                config,
+                "nvidia-smi",
+                &[
+                    "--query-gpu=utilization.gpu",
+                    "--format=csv,noheader,nounits",
+                ],
+            )?
+            .lines()
+            .next()?
+            .trim()
+            .parse()
+            .ok()
+        })
+}
+
+/// Detects whether the system runs on AC power, through the mains supplies such as `AC` or `ADP1`.
+/// Returns `None` without a mains supply, which is common on desktops and servers.
+fn get_on_ac_power() -> Option<bool> {
+    let online = dir_entries_with_prefix("/sys/class/power_supply", "")
+        .into_iter()
+        .filter(|supply| read_sysfs(supply.join("type")).as_deref() == Some("Mains"))
+        .filter_map(|supply| read_sysfs(supply.join("online")))
+        .collect::<Vec<_>>();
+    (!online.is_empty()).then(|| online.iter().any(|online| online == "1"))
+}
+
+/// Fetches the installed Flatpak version, for example `1.14.4`.
+fn get_flatpak_version(config: &Config) -> Option<String> {
+    // For example `Flatpak 1.14.4`.
+    let version = command_output(config, "flatpak", &["--version"])?;
+    Some(version.split_whitespace().next_back()?.to_owned())
+}
+
+/// Fetches the installed snap version, for example `2.58.3`.
+fn get_snap_version(config: &Config) -> Option<String> {
+    // Lists the versions of `snap`, `snapd`, the series and the distro, one per line.
+    command_output(config, "snap", &["version"])?
+        .lines()
+        .find_map(|line| line.strip_prefix("snap "))
+        .map(|version| version.trim().to_owned())
+}
+
+/// Parses the `nameserver` entries of a `resolv.conf` file, without duplicates.
+fn parse_resolv_conf(path: &str) -> Vec<IpAddr> {
+    let mut servers: Vec<IpAddr> = Vec::new();
+    for line in read_to_string(path).unwrap_or_default().lines() {
+        // IPv6 link-local servers may carry a zone, such as `fe80::1%eth0`.
+        let server = line
+            .strip_prefix("nameserver")
+            .and_then(|server| server.split_whitespace().next())
+            .and_then(|server| server.split('%').next()?.parse().ok());
+        if let Some(server) = server.filter(|server| !servers.contains(server)) {
+            servers.push(server);
+        }
+    }
+
+    servers
+}
+
+/// Fetches the configured DNS servers.
+/// When `/etc/resolv.conf` only points at the systemd-resolved stub, its upstream servers are
+/// reported instead.
+fn get_dns_servers() -> Vec<IpAddr> {
+    const RESOLVED_STUB: IpAddr = IpAddr::V4(Ipv4Addr::new(127, 0, 0, 53));
+    let servers = parse_resolv_conf("/etc/resolv.conf");
+    if servers.iter().all(|server| *server == RESOLVED_STUB) {
+        let upstream = parse_resolv_conf("/run/systemd/resolve/resolv.conf");
+        if !upstream.is_empty() {
+            return upstream;
+        }
+    }
+
+    servers
+}
+
+/// Splits a kernel command line into its parameters, with the value of `key=value` parameters.
+/// Quoted values may contain spaces, for example `dyndbg="file foo.c +p"`.
+fn parse_kernel_cmdline(cmdline: &str) -> Vec<(String, Option<String>)> {
+    let mut params = Vec::new();
+    let mut param = String::new();
+    let mut quoted = false;
+    for char in cmdline.chars().chain([' ']) {
+        match char {
+            '"' => quoted = !quoted,
+            _ if char.is_whitespace() && !quoted => {
+                if !param.is_empty() {
+                    params.push(match param.split_once('=') {
+                        Some((key, value)) => (key.to_owned(), Some(value.to_owned())),
+                        None => (param.clone(), None),
+                    });
+                    param.clear();
+                }
+            }
+            _ => param.push(char),
+        }
+    }
+
+    params
+}
+
+/// Fetches the OpenGL renderer through `glxinfo`, for example `AMD Radeon RX 6800 (radeonsi, ...)`.
+/// Returns `None` without a graphical session, as `glxinfo` needs a display to connect to.
+fn get_opengl_renderer(config: &Config) -> Option<String> {
+    if std::env::var_os("DISPLAY").is_none() && std::env::var_os("WAYLAND_DISPLAY").is_none() {
+        return None;
+    }
+
+    command_output(config, "glxinfo", &["-B"])?
+        .lines()
+        .find_map(|line| line.trim().strip_prefix("OpenGL renderer string:"))
+        .map(|renderer| renderer.trim().to_owned())
+}
+
+/// Lists the names of the Vulkan devices through `vulkaninfo`, which lists them as
+/// `deviceName = AMD Radeon RX 6800` lines.
+fn get_vulkan_devices(config: &Config) -> Vec<String> {
+    command_output(config, "vulkaninfo", &["--summary"])
+        .unwrap_or_default()
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.split_once('=')?;
+            (key.trim() == "deviceName").then(|| value.trim().to_owned())
+        })
+        .collect()
+}
 
-/// Simple macro to convert all bytes to their u8 representation.
-macro_rules! bytes_to_u8 {
-    ($collection:expr) => {
-        $collection
-            .iter()
-            .map(|byte| *byte as u8)
-            .collect::<Vec<_>>()
+/// Returns the first of `names` found running, from `/proc/<pid>/comm`.
+fn find_running_process<'a>(names: &[&'a str]) -> Option<&'a str> {
+    read_dir("/proc")
+        .ok()?
+        .flatten()
+        .filter(|entry| {
+            entry
+                .file_name()
+                .to_string_lossy()
+                .bytes()
+                .all(|byte| byte.is_ascii_digit())
+        })
+        .find_map(|entry| {
+            let comm = read_sysfs(entry.path().join("comm"))?;
+            names.iter().copied().find(|name| *name == comm)
+        })
+}
+
+/// Detects a sway, i3 or bspwm session from the environment.
+fn get_tiling_wm() -> Option<&'static str> {
+    // sway also sets `I3SOCK`, for compatibility with i3 tools.
+    if std::env::var_os("SWAYSOCK").is_some() {
+        return Some("sway");
+    }
+    if std::env::var_os("I3SOCK").is_some() {
+        return Some("i3");
+    }
+
+    ["XDG_CURRENT_DESKTOP", "DESKTOP_SESSION"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find_map(|desktop| {
+            let desktop = desktop.to_lowercase();
+            ["sway", "i3", "bspwm"]
+                .into_iter()
+                .find(|wm| desktop.split([':', '-']).any(|part| part == *wm))
+        })
+}
+
+/// Detects the look of tiling window managers, which don't follow the GTK theme: the focused
+/// window colors from the sway, i3 or bspwm config, and the compositor if one is running.
+/// Processes are only looked through for the compositor once the environment shows a session of
+/// one of those window managers.
+/// For example: `#4c7899 #285577 #ffffff (picom)`
+fn get_wm_theme() -> Option<String> {
+    let wm = get_tiling_wm()?;
+    let read_config = |path: &str| read_to_string(config_dir()?.join(path)).ok();
+
+    // sway and i3 set the border, background and text colors through `client.focused`, bspwm
+    // through `bspc config focused_border_color`.
+    let colors = if wm == "bspwm" {
+        read_config("bspwm/bspwmrc").and_then(|bspwmrc| {
+            bspwmrc.lines().find_map(|line| {
+                let color = line
+                    .trim()
+                    .strip_prefix("bspc config focused_border_color")?;
+                Some(color.trim().trim_matches(['"', '\'']).to_owned())
+            })
+        })
+    } else {
+        read_config("sway/config")
+            .filter(|_| wm == "sway")
+            .or_else(|| read_config("i3/config"))
+            .and_then(|config| {
+                config.lines().find_map(|line| {
+                    let colors = line.trim().strip_prefix("client.focused ")?;
+                    Some(colors.split_whitespace().collect::<Vec<_>>().join(" "))
+                })
+            })
     };
+    let compositor = find_running_process(&["picom", "compton"]);
+
+    match (colors, compositor) {
+        (Some(colors), Some(compositor)) => Some(format!("{colors} ({compositor})")),
+        (Some(colors), None) => Some(colors),
+        (None, compositor) => compositor.map(str::to_owned),
+    }
 }
 
-/// Fetched system information.
-#[derive(Debug)]
-pub struct SystemInfo {
-    pub distro_name: String,
-    pub distro_id: String,
-    pub distro_build_id: String,
-    pub username: String,
-    pub hostname: String,
-    pub shell: String,
-    pub kernel: String,
-    pub uptime_seconds: u32,
-    pub uptime_minutes: u32,
-    pub uptime_hours: u32,
-    pub uptime_days: u32,
-    pub uptime_formatted: String,
-    pub total_mem: String,
-    pub cached_mem: String,
-    pub available_mem: String,
-    pub used_mem: String,
+/// Detects the firewall, preferring an active ufw or firewalld frontend over the rules it manages.
+/// Otherwise the loaded nftables or iptables rules decide, and an installed but inactive frontend
+/// is only reported when those can't be listed, which usually needs root.
+fn get_firewall(config: &Config) -> Option<FirewallStatus> {
+    let status = |backend: &str, active| {
+        Some(FirewallStatus {
+            backend: backend.to_owned(),
+            active,
+        })
+    };
+
+    // Frontends are often installed but disabled, as ufw is on Ubuntu, in which case rules may
+    // still be loaded directly, so only an active frontend ends the search.
+    let mut inactive_frontend = None;
+    if let Some(enabled) = read_to_string("/etc/ufw/ufw.conf")
+        .ok()
+        .and_then(|ufw| parse_key_value_file(&ufw, "ENABLED"))
+    {
+        if enabled == "yes" {
+            return status("ufw", true);
+        }
+        inactive_frontend = Some("ufw");
+    }
+
+    if config.allow_subprocess
+        && (Path::new("/usr/lib/firewalld").exists() || Path::new("/etc/firewalld").exists())
+    {
+        // `firewall-cmd --state` prints `running`, or fails with `not running`.
+        if command_output(config, "firewall-cmd", &["--state"]).as_deref() == Some("running") {
+            return status("firewalld", true);
+        }
+        inactive_frontend = inactive_frontend.or(Some("firewalld"));
+    }
+
+    if let Some(ruleset) = command_output(config, "nft", &["list", "ruleset"]) {
+        return status("nftables", !ruleset.is_empty());
+    }
+
+    // Without any rules, `iptables -S` only lists the default `-P` policies.
+    if let Some(rules) = command_output(config, "iptables", &["-S"]) {
+        return status(
+            "iptables",
+            rules.lines().any(|rule| !rule.starts_with("-P ")),
+        );
+    }
+
+    // The rules couldn't be listed, usually for lack of root, so fall back to the frontend.
+    inactive_frontend.and_then(|frontend| status(frontend, false))
 }
 
-/// Uptime structure.
-struct Uptime {
-    formatted: String,
-    seconds: u32,
-    minutes: u32,
-    hours: u32,
-    days: u32,
+/// Counts the logical CPUs that share the first CPU's cache of the given `level`, such as 3 for
+/// the L3 cache.
+fn get_cache_shared_by(level: u8) -> Option<u32> {
+    // The `indexN` numbering differs between CPUs, so the cache is looked up by its level.
+    dir_entries_with_prefix("/sys/devices/system/cpu/cpu0/cache", "index")
+        .into_iter()
+        .find(|cache| {
+            read_sysfs(cache.join("level")) == Some(level.to_string())
+                && read_sysfs(cache.join("type")).as_deref() != Some("Instruction")
+        })
+        .and_then(|cache| read_sysfs(cache.join("shared_cpu_list")))
+        .map(|list| parse_cpu_list(&list).len() as u32)
+        .filter(|count| *count > 0)
 }
 
-/// Type of information to obtain.
-#[derive(PartialEq)]
-pub enum Type {
-    Username,
-    HostName,
-    KernelVersion,
+/// Reads whether CPU boost, also known as turbo, is enabled.
+fn get_cpu_boost() -> Option<bool> {
+    match read_sysfs("/sys/devices/system/cpu/cpufreq/boost").as_deref() {
+        Some("1") => return Some(true),
+        Some("0") => return Some(false),
+        _ => {}
+    }
+
+    // intel_pstate reports the inverse, whether turbo is disabled.
+    match read_sysfs("/sys/devices/system/cpu/intel_pstate/no_turbo").as_deref() {
+        Some("1") => Some(false),
+        Some("0") => Some(true),
+        _ => None,
+    }
 }
 
-/// Parses the given os-release key as a `String`.
-fn parse_osr_key(os_release: &str, key: &str) -> Option<String> {
-    let mut split = os_release.split(&format!("{key}=")).nth(1)?.to_owned();
-    if split.contains('\n') {
-        // Only get the first line from the result.
-        split = split.split('\n').next()?.to_owned()
+/// Detects the active mandatory access control system, SELinux or AppArmor.
+fn get_mac_system() -> Option<MacStatus> {
+    let selinux_mode = match read_sysfs("/sys/fs/selinux/enforce").as_deref() {
+        Some("1") => Some("Enforcing"),
+        Some("0") => Some("Permissive"),
+        _ => None,
+    };
+    if let Some(mode) = selinux_mode {
+        return Some(MacStatus {
+            kind: "SELinux".to_owned(),
+            mode: mode.to_owned(),
+        });
+    }
+
+    if read_sysfs("/sys/module/apparmor/parameters/enabled").as_deref() == Some("Y") {
+        return Some(MacStatus {
+            kind: "AppArmor".to_owned(),
+            mode: read_sysfs("/sys/module/apparmor/parameters/mode")
+                .unwrap_or_else(|| "enabled".to_owned()),
+        });
+    }
+
+    None
+}
+
+/// Converts a socket address into an IP address, if it's an IPv4 or IPv6 one.
+#[cfg(unix)]
+unsafe fn sockaddr_to_ip(address: *const libc::sockaddr) -> Option<IpAddr> {
+    if address.is_null() {
+        return None;
+    }
+
+    match i32::from((*address).sa_family) {
+        libc::AF_INET => {
+            let address = &*(address as *const libc::sockaddr_in);
+            Some(IpAddr::V4(Ipv4Addr::from(u32::from_be(
+                address.sin_addr.s_addr,
+            ))))
+        }
+        libc::AF_INET6 => {
+            let address = &*(address as *const libc::sockaddr_in6);
+            Some(IpAddr::V6(Ipv6Addr::from(address.sin6_addr.s6_addr)))
+        }
+        _ => None,
+    }
+}
+
+/// Fetches the network interfaces and their addresses through `getifaddrs`.
+#[cfg(unix)]
+fn get_interfaces() -> Vec<NetInterface> {
+    let mut interfaces: Vec<NetInterface> = Vec::new();
+    let mut addresses = std::ptr::null_mut();
+    if unsafe { libc::getifaddrs(&mut addresses) } != 0 {
+        return interfaces;
+    }
+
+    // `getifaddrs` returns one entry per address, so entries of the same interface are merged.
+    let mut current = addresses;
+    while !current.is_null() {
+        let entry = unsafe { &*current };
+        current = entry.ifa_next;
+
+        let name = unsafe { CStr::from_ptr(entry.ifa_name) }
+            .to_string_lossy()
+            .into_owned();
+        let index = match interfaces
+            .iter()
+            .position(|interface| interface.name == name)
+        {
+            Some(index) => index,
+            None => {
+                let sysfs = Path::new("/sys/class/net").join(&name);
+                let is_virtual = !sysfs.join("device").exists();
+                // The speed is meaningless for wireless and virtual interfaces, and reads as `-1`
+                // or fails while the interface is down.
+                let speed_mbps = if is_virtual || sysfs.join("wireless").exists() {
+                    None
+                } else {
+                    read_sysfs(sysfs.join("speed")).and_then(|speed| speed.parse().ok())
+                };
+                interfaces.push(NetInterface {
+                    name,
+                    up: false,
+                    is_virtual,
+                    addresses: Vec::new(),
+                    speed_mbps,
+                });
+                interfaces.len() - 1
+            }
+        };
+
+        let interface = &mut interfaces[index];
+        interface.up |= entry.ifa_flags & libc::IFF_UP as u32 != 0;
+        if let Some(address) = unsafe { sockaddr_to_ip(entry.ifa_addr) } {
+            interface.addresses.push(address);
+        }
     }
+    unsafe { libc::freeifaddrs(addresses) };
 
-    if split.contains('"') {
-        // Don't keep double-quotes.
-        split = split.replace('"', "")
+    interfaces
+}
+
+/// Finds the fully qualified domain name of `hostname`, similar to `hostname -f`: the hostname
+/// itself if it has a domain, its full name in `/etc/hosts`, or through the resolver if allowed.
+fn get_fqdn(config: &Config, hostname: &str) -> Option<String> {
+    if hostname.contains('.') {
+        return Some(hostname.to_owned());
     }
 
-    Some(split)
+    read_to_string("/etc/hosts")
+        .ok()
+        .and_then(|hosts| parse_hosts_fqdn(&hosts, hostname))
+        .or_else(|| config.resolve_fqdn.then(|| resolve_fqdn(hostname))?)
 }
 
-/// Parses the given MemInfo key as a `String`.
-fn parse_minf_key(meminfo: &str, key: &str) -> Option<String> {
-    let line = meminfo.lines().find(|line| line.starts_with(key))?;
-    Some(line.split_whitespace().nth(1)?.to_owned())
+/// Finds a `hostname.domain` name in the contents of `/etc/hosts`.
+fn parse_hosts_fqdn(hosts: &str, hostname: &str) -> Option<String> {
+    hosts
+        .lines()
+        .flat_map(|line| {
+            let line = line.split('#').next().unwrap_or_default();
+            // The first column is the address.
+            line.split_whitespace().skip(1)
+        })
+        .find(|name| {
+            name.strip_prefix(hostname)
+                .and_then(|domain| domain.strip_prefix('.'))
+                .is_some_and(|domain| !domain.is_empty())
+        })
+        .map(str::to_owned)
 }
 
-/// Converts the value of the given MemInfo key, into the gigabytes representation.
-fn minf_get_gb(meminfo: &str, key: &str) -> String {
-    let parsed: f64 = parse_minf_key(meminfo, key).unwrap().parse().unwrap();
-    kb_to_gb(parsed)
+/// Resolves the canonical name of `hostname` through `getaddrinfo`, which can block for seconds.
+#[cfg(unix)]
+fn resolve_fqdn(hostname: &str) -> Option<String> {
+    let node = CString::new(hostname).ok()?;
+    let mut hints = unsafe { MaybeUninit::<libc::addrinfo>::zeroed().assume_init() };
+    hints.ai_flags = libc::AI_CANONNAME;
+    hints.ai_family = libc::AF_UNSPEC;
+    let mut addresses = std::ptr::null_mut();
+    let code =
+        unsafe { libc::getaddrinfo(node.as_ptr(), std::ptr::null(), &hints, &mut addresses) };
+    if code != 0 {
+        log!("Failed resolving {hostname}: getaddrinfo returned {code}");
+        return None;
+    }
+
+    // Only the first entry carries the canonical name.
+    let canonical_name = unsafe { (*addresses).ai_canonname };
+    let fqdn = (!canonical_name.is_null()).then(|| {
+        unsafe { CStr::from_ptr(canonical_name) }
+            .to_string_lossy()
+            .into_owned()
+    });
+    unsafe { libc::freeaddrinfo(addresses) };
+    fqdn
 }
 
-/// Converts kilobytes to gigabytes.
-fn kb_to_gb(number: f64) -> String {
-    let unit = Byte::from_unit(number, byte_unit::ByteUnit::KB).unwrap();
-    unit.get_adjusted_unit(byte_unit::ByteUnit::GB).to_string()
+/// The FQDN isn't resolved on Windows.
+#[cfg(windows)]
+fn resolve_fqdn(_hostname: &str) -> Option<String> {
+    None
+}
+
+/// `getifaddrs` isn't available on Windows.
+#[cfg(windows)]
+fn get_interfaces() -> Vec<NetInterface> {
+    Vec::new()
+}
+
+/// Detects whether IPv6 is enabled, either for every interface or through a global IPv6 address
+/// on one of `interfaces`. Kernels built without IPv6 report `false`.
+fn get_ipv6_enabled(interfaces: &[NetInterface]) -> bool {
+    match read_sysfs("/proc/sys/net/ipv6/conf/all/disable_ipv6").as_deref() {
+        None => false,
+        Some("0") => true,
+        Some(_) => interfaces
+            .iter()
+            .flat_map(|interface| &interface.addresses)
+            .any(|address| match address {
+                // Loopback and link-local (`fe80::/10`) addresses aren't global.
+                IpAddr::V6(address) => {
+                    !address.is_loopback() && address.segments()[0] & 0xFFC0 != 0xFE80
+                }
+                IpAddr::V4(_) => false,
+            }),
+    }
+}
+
+/// Name prefixes of tunnel interfaces created by VPN clients.
+const VPN_INTERFACE_PREFIXES: &[&str] = &["tun", "wg", "ppp"];
+
+/// Picks the names of the VPN tunnel interfaces that are up out of `interfaces`.
+fn get_vpn_interfaces(interfaces: &[NetInterface]) -> Vec<String> {
+    interfaces
+        .iter()
+        .filter(|interface| {
+            interface.up
+                && VPN_INTERFACE_PREFIXES
+                    .iter()
+                    .any(|prefix| interface.name.starts_with(prefix))
+        })
+        .map(|interface| interface.name.clone())
+        .collect()
+}
+
+/// Counts the CPU cores the current process is allowed to run on.
+/// This can be lower than the core count of the host when restricted by cpusets or `taskset`.
+#[cfg(unix)]
+fn get_cpu_affinity_count() -> Option<u32> {
+    let mut set = unsafe { MaybeUninit::<libc::cpu_set_t>::zeroed().assume_init() };
+    if unsafe { libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set) } != 0
+    {
+        log!(
+            "sched_getaffinity failed: {}",
+            std::io::Error::last_os_error()
+        );
+        return None;
+    }
+
+    Some(unsafe { libc::CPU_COUNT(&set) } as u32)
+}
+
+/// `sched_getaffinity` isn't available on Windows.
+#[cfg(windows)]
+fn get_cpu_affinity_count() -> Option<u32> {
+    None
+}
+
+/// Small ASCII logos of common distros, keyed by their os-release `ID`.
+const DISTRO_LOGOS: &[(&str, &str)] = &[
+    (
+        "arch",
+        r"      /\
+     /  \
+    /\   \
+   /  __  \
+  /  (  )  \
+ / __|  |__\
+/.`        `.\",
+    ),
+    (
+        "debian",
+        r"  _____
+ /  __ \
+|  /    |
+|  \___-
+-_
+  --_",
+    ),
+    (
+        "ubuntu",
+        r"         _
+     ---(_)
+ _/  ---  \
+(_) |   |
+  \  --- _/
+     ---(_)",
+    ),
+    (
+        "fedora",
+        r"      _____
+     /   __)\
+     |  /  \ \
+  ___|  |__/ /
+ / (_    _)_/
+/ /  |  |
+\ \__/  |
+ \(_____/",
+    ),
+    (
+        "gentoo",
+        r"  _-----_
+ (       \
+ \    0   \
+  \        )
+  /      _/
+ (     _-
+ \____-",
+    ),
+];
+
+/// Logo for distros without one in `DISTRO_LOGOS`.
+const TUX_LOGO: &str = r"    .--.
+   |o_o |
+   |:_/ |
+  //   \ \
+ (|     | )
+/'\_   _/`\
+\___)=(___/";
+
+/// Returns the ASCII logo of the distro with the given os-release `ID`, or Tux for unknown distros.
+fn get_distro_ascii(distro_id: &str) -> &'static str {
+    DISTRO_LOGOS
+        .iter()
+        .find(|(id, _)| *id == distro_id)
+        .map_or(TUX_LOGO, |(_, logo)| logo)
+}
+
+/// Files that mark an installed bootloader, checked in order.
+const BOOTLOADER_MARKERS: &[(&str, &str)] = &[
+    ("/boot/grub/grub.cfg", "GRUB"),
+    ("/boot/grub2/grub.cfg", "GRUB"),
+    ("/boot/loader/loader.conf", "systemd-boot"),
+    ("/boot/efi/loader/loader.conf", "systemd-boot"),
+    ("/efi/loader/loader.conf", "systemd-boot"),
+    ("/etc/default/rEFInd", "rEFInd"),
+    ("/boot/syslinux", "syslinux"),
+];
+
+/// Detects the installed bootloader through its marker files.
+fn get_bootloader() -> Option<String> {
+    BOOTLOADER_MARKERS
+        .iter()
+        .find(|(marker, _)| Path::new(marker).exists())
+        .map(|(_, name)| (*name).to_owned())
+}
+
+/// Reads a sysctl through `/proc/sys`, for example `vm.swappiness`.
+/// Returns `None` for unknown names and names that aren't made up of plain components, so it can't
+/// be used to read files outside of `/proc/sys`.
+pub fn sysctl(name: &str) -> Option<String> {
+    let is_valid_component = |component: &str| {
+        !component.is_empty()
+            && component
+                .chars()
+                .all(|char| char.is_ascii_alphanumeric() || char == '_' || char == '-')
+    };
+    if !name.split('.').all(is_valid_component) {
+        return None;
+    }
+
+    read_sysfs(format!("/proc/sys/{}", name.replace('.', "/")))
 }
 
 /// Fetches certan system info through `libc`.
+#[cfg(unix)]
 pub fn get_by_type(r#type: Type) -> Option<String> {
     // Create an uninitialized instance of `utsname`.
     let mut info = unsafe { MaybeUninit::<libc::utsname>::zeroed().assume_init() };
@@ -93,16 +3677,16 @@ pub fn get_by_type(r#type: Type) -> Option<String> {
     }
 
     let result = match r#type {
-        Type::Username => unsafe {
-            CStr::from_ptr(libc::getlogin())
-                .to_str()
-                .expect("[ERROR] Failed retrieving username!")
-                .to_owned()
-        },
+        Type::Username => user_info(unsafe { libc::getuid() })?.name,
         Type::HostName => String::from_utf8(bytes_to_u8!(info.nodename))
-            .expect("[ERROR] Failed converting libc HostName output to a String!"),
+            .map_err(|_| log!("Failed converting libc HostName output to a String!"))
+            .ok()?,
         Type::KernelVersion => String::from_utf8(bytes_to_u8!(info.release))
-            .expect("[ERROR] Failed converting libc KernelVersion output to a String!"),
+            .map_err(|_| log!("Failed converting libc KernelVersion output to a String!"))
+            .ok()?,
+        Type::KernelBuild => String::from_utf8(bytes_to_u8!(info.version))
+            .map_err(|_| log!("Failed converting libc KernelBuild output to a String!"))
+            .ok()?,
     };
 
     Some(if result.contains('\0') {
@@ -113,17 +3697,143 @@ pub fn get_by_type(r#type: Type) -> Option<String> {
     })
 }
 
-/// Returns the uptime.
+/// Fetches the account of the user with the given `uid` from the password database.
+#[cfg(unix)]
+pub fn user_info(uid: u32) -> Option<UserInfo> {
+    let mut passwd = unsafe { MaybeUninit::<libc::passwd>::zeroed().assume_init() };
+    let mut result = std::ptr::null_mut();
+    let mut buffer: Vec<libc::c_char> = vec![0; 1024];
+    loop {
+        let code = unsafe {
+            libc::getpwuid_r(
+                uid,
+                &mut passwd,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut result,
+            )
+        };
+        match code {
+            // The entry didn't fit, retry with a bigger buffer.
+            libc::ERANGE => buffer.resize(buffer.len() * 2, 0),
+            0 if !result.is_null() => break,
+            _ => {
+                log!("Failed looking up the user with UID {uid}");
+                return None;
+            }
+        }
+    }
+
+    let field = |field| {
+        unsafe { CStr::from_ptr(field) }
+            .to_string_lossy()
+            .into_owned()
+    };
+    Some(UserInfo {
+        name: field(passwd.pw_name),
+        home: PathBuf::from(field(passwd.pw_dir)),
+        shell: field(passwd.pw_shell),
+        uid: passwd.pw_uid,
+        gid: passwd.pw_gid,
+    })
+}
+
+/// There's no password database on Windows.
+#[cfg(windows)]
+pub fn user_info(_uid: u32) -> Option<UserInfo> {
+    None
+}
+
+/// Converts a fixed-size, nul-padded C string into a `String`.
+#[cfg(unix)]
+fn c_chars_to_string(chars: &[libc::c_char]) -> String {
+    let bytes = bytes_to_u8!(chars);
+    let end = bytes
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+/// Returns the logged-in user sessions from utmp, similar to `who`.
+/// Returns an empty `Vec` when utmp is unavailable.
+/// The utmp functions aren't thread-safe, so avoid calling this from multiple threads at once.
+#[cfg(unix)]
+pub fn sessions() -> Vec<Session> {
+    let mut sessions = Vec::new();
+    unsafe {
+        libc::setutxent();
+        loop {
+            let entry = libc::getutxent();
+            if entry.is_null() {
+                break;
+            }
+
+            // Skip boot, init and dead process records.
+            let entry = &*entry;
+            if entry.ut_type != libc::USER_PROCESS {
+                continue;
+            }
+
+            let remote_host = c_chars_to_string(&entry.ut_host);
+            sessions.push(Session {
+                user: c_chars_to_string(&entry.ut_user),
+                tty: c_chars_to_string(&entry.ut_line),
+                login_time: UNIX_EPOCH + Duration::from_secs(entry.ut_tv.tv_sec as u64),
+                remote_host: (!remote_host.is_empty()).then_some(remote_host),
+            });
+        }
+        libc::endutxent();
+    }
+
+    sessions
+}
+
+/// There's no utmp on Windows.
+#[cfg(windows)]
+pub fn sessions() -> Vec<Session> {
+    Vec::new()
+}
+
+/// Fetches certain system info through the environment.
+/// The kernel version isn't available on Windows.
+#[cfg(windows)]
+pub fn get_by_type(r#type: Type) -> Option<String> {
+    match r#type {
+        Type::Username => std::env::var("USERNAME").ok(),
+        Type::HostName => std::env::var("COMPUTERNAME").ok(),
+        Type::KernelVersion | Type::KernelBuild => None,
+    }
+}
+
+/// Re-reads the uptime every `interval` and passes it to `f`, until `stop` is set.
+/// This blocks the calling thread, so run it on its own thread for live displays.
+pub fn watch_uptime(interval: Duration, stop: &AtomicBool, mut f: impl FnMut(Uptime)) {
+    while !stop.load(Ordering::Relaxed) {
+        // Skip the tick rather than give up if the read happens to fail.
+        if let Ok(uptime) = read_to_string("/proc/uptime") {
+            f(parse_uptime(&uptime));
+        }
+
+        thread::sleep(interval);
+    }
+}
+
+/// Parses the uptime from the contents of `/proc/uptime`.
+fn parse_uptime(uptime: &str) -> Uptime {
+    uptime_from_seconds(
+        uptime
+            .split('.')
+            .next()
+            .unwrap_or_default()
+            .parse()
+            .unwrap_or_default(),
+    )
+}
+
+/// Splits and pretty-formats an uptime given in seconds.
 /// For example: `1 day, 1 hour, 20 minutes`
-fn get_uptime() -> Uptime {
-    let total_seconds =
-        read_to_string("/proc/uptime").expect("[ERROR] Failed reading /proc/uptime!");
-    let total_seconds: u32 = total_seconds
-        .split('.')
-        .next()
-        .unwrap_or_default()
-        .parse()
-        .unwrap_or_default();
+fn uptime_from_seconds(total_seconds: u32) -> Uptime {
     let days = total_seconds / 86400;
     let hours = total_seconds / 3600;
     let minutes = total_seconds % 3600 / 60;
@@ -179,39 +3889,225 @@ fn get_uptime() -> Uptime {
     }
 }
 
-/// Fetches system information.
-/// This can panic if it fails fetching properly.
-pub fn get_system_information() -> Option<SystemInfo> {
-    let os_release =
-        read_to_string("/etc/os-release").expect("[ERROR] Failed reading /etc/os-release!");
-    let meminfo = read_to_string("/proc/meminfo").expect("[ERROR] Failed reading /etc/meminfo!");
-    let distro_name = parse_osr_key(&os_release, "NAME")?;
-    let distro_id = parse_osr_key(&os_release, "ID")?;
-    let distro_build_id = parse_osr_key(&os_release, "BUILD_ID")?;
+/// Detects the container runtime that the system is running under.
+/// For example: `Podman 4.4.1`, `Docker` or `LXC`
+fn get_container_runtime() -> Option<String> {
+    // Podman describes itself in /run/.containerenv, for example `engine="podman-4.4.1"`.
+    let containerenv = read_to_string("/run/.containerenv").ok();
+    // The `container` variable is set for PID 1, so fall back to its environment if it wasn't
+    // passed down to us.
+    let container_var = std::env::var("container").ok().or_else(|| {
+        read_to_string("/proc/1/environ")
+            .ok()?
+            .split('\0')
+            .find_map(|var| var.strip_prefix("container=").map(str::to_owned))
+    });
+
+    if container_var.as_deref() == Some("podman") || containerenv.is_some() {
+        let version = containerenv
+            .and_then(|contents| parse_osr_key(&contents, "engine"))
+            .and_then(|engine| engine.strip_prefix("podman-").map(str::to_owned));
+        return Some(match version {
+            Some(version) => format!("Podman {version}"),
+            None => "Podman".to_owned(),
+        });
+    }
+
+    let cgroup = read_to_string("/proc/1/cgroup").unwrap_or_default();
+    if Path::new("/.dockerenv").exists() || cgroup.contains("/docker") {
+        return Some("Docker".to_owned());
+    }
+
+    if container_var.as_deref() == Some("lxc") || cgroup.contains("/lxc") {
+        return Some("LXC".to_owned());
+    }
+
+    None
+}
+
+/// Formats the time since boot relative to now, using the largest fitting unit.
+/// For example: `3 hours ago`, or `just now` for the first minute.
+fn format_booted_ago(uptime_seconds: u32) -> String {
+    let (amount, unit) = match uptime_seconds {
+        0..=59 => return "just now".to_owned(),
+        60..=3599 => (uptime_seconds / 60, "minute"),
+        3600..=86399 => (uptime_seconds / 3600, "hour"),
+        _ => (uptime_seconds / 86400, "day"),
+    };
+
+    format!("{amount} {unit}{} ago", if amount > 1 { "s" } else { "" })
+}
+
+/// Contents of the system files that the information is parsed from.
+struct Sources {
+    os_release: String,
+    meminfo: String,
+    uptime: String,
+    /// Empty when `/proc/cpuinfo` couldn't be read, as none of the CPU fields are required.
+    cpuinfo: String,
+}
+
+/// Fetches system information with the default `Config`.
+pub fn get_system_information() -> Result<SystemInfo, InfoError> {
+    get_system_information_with(&Config::default())
+}
+
+/// Fetches system information with the given `config`.
+#[cfg(not(windows))]
+pub fn get_system_information_with(config: &Config) -> Result<SystemInfo, InfoError> {
+    let sources = Sources {
+        os_release: read_required("/etc/os-release")
+            .or_else(|error| termux_os_release(config).ok_or(error))?,
+        meminfo: read_required("/proc/meminfo")?,
+        uptime: read_required("/proc/uptime")?,
+        cpuinfo: read_to_string("/proc/cpuinfo").unwrap_or_default(),
+    };
+
+    build_system_information(sources, config)
+}
+
+/// Fetching system information isn't supported on Windows.
+#[cfg(windows)]
+pub fn get_system_information_with(_config: &Config) -> Result<SystemInfo, InfoError> {
+    Err(InfoError::Unsupported)
+}
+
+/// Fetches system information with the default `Config`, without blocking the executor.
+/// The detectors run on tokio's blocking thread pool, so this needs a tokio runtime.
+#[cfg(feature = "async")]
+pub async fn get_system_information_async() -> Result<SystemInfo, InfoError> {
+    get_system_information_async_with(&Config::default()).await
+}
+
+/// Fetches system information with the given `config` without blocking the executor.
+/// The required system files are read through `tokio::fs`, while the detectors, which read many
+/// more files, resolve the FQDN and run programs, are moved to tokio's blocking thread pool.
+#[cfg(all(feature = "async", not(windows)))]
+pub async fn get_system_information_async_with(config: &Config) -> Result<SystemInfo, InfoError> {
+    async fn read(path: &'static str) -> Result<String, InfoError> {
+        tokio::fs::read_to_string(path)
+            .await
+            .map_err(|source| read_error(path, source))
+    }
+
+    let os_release = read("/etc/os-release").await;
+    let meminfo = read("/proc/meminfo").await?;
+    let uptime = read("/proc/uptime").await?;
+    let cpuinfo = tokio::fs::read_to_string("/proc/cpuinfo")
+        .await
+        .unwrap_or_default();
+
+    let config = config.clone();
+    tokio::task::spawn_blocking(move || {
+        let sources = Sources {
+            // The Termux fallback runs `getprop`, so it's part of the blocking work.
+            os_release: os_release.or_else(|error| termux_os_release(&config).ok_or(error))?,
+            meminfo,
+            uptime,
+            cpuinfo,
+        };
+        build_system_information(sources, &config)
+    })
+    .await
+    .unwrap_or_else(|error| match error.try_into_panic() {
+        Ok(panic) => std::panic::resume_unwind(panic),
+        Err(_) => Err(InfoError::Cancelled),
+    })
+}
+
+/// Fetching system information isn't supported on Windows.
+#[cfg(all(feature = "async", windows))]
+pub async fn get_system_information_async_with(_config: &Config) -> Result<SystemInfo, InfoError> {
+    Err(InfoError::Unsupported)
+}
+
+/// Builds an os-release for Termux on Android, which doesn't ship `/etc/os-release`.
+/// Returns `None` outside of Termux.
+fn termux_os_release(config: &Config) -> Option<String> {
+    if !std::env::var("PREFIX").is_ok_and(|prefix| prefix.starts_with("/data/data/com.termux")) {
+        return None;
+    }
+
+    let android_version =
+        command_output(config, "getprop", &["ro.build.version.release"]).unwrap_or_default();
+    Some(format!(
+        "NAME=\"Android (Termux)\"\nID=termux\nID_LIKE=android\nBUILD_ID={android_version}\nHOME_URL=\"https://termux.dev/\"\n"
+    ))
+}
+
+/// Parses the system information out of the already-read `sources`.
+fn build_system_information(sources: Sources, config: &Config) -> Result<SystemInfo, InfoError> {
+    let Sources {
+        os_release,
+        meminfo,
+        uptime,
+        cpuinfo,
+    } = sources;
+    let fetched_at = SystemTime::now();
+    let kernel_cmdline = read_sysfs("/proc/cmdline");
+    let osr_key = |key| parse_osr_key(&os_release, key).ok_or(InfoError::Missing(key));
+    let distro_name = osr_key("NAME")?;
+    let distro_id = osr_key("ID")?;
+    let distro_ascii = get_distro_ascii(&distro_id);
+    let distro_build_id = osr_key("BUILD_ID")?;
+    let distro_logo = parse_osr_key(&os_release, "LOGO");
+    let distro_home_url = parse_osr_key(&os_release, "HOME_URL");
 
-    let username = get_by_type(Type::Username)?;
-    let hostname = get_by_type(Type::HostName)?;
+    let username = get_by_type(Type::Username).ok_or(InfoError::Missing("username"))?;
+    let hostname = get_by_type(Type::HostName).ok_or(InfoError::Missing("hostname"))?;
+    let fqdn = get_fqdn(config, &hostname);
     let shell = std::env::var("SHELL")
-        .expect("[ERROR] Failed getting the value of the SHELL environment variable!")
-        .split('/')
-        .last()?
-        .to_owned();
-    let kernel = get_by_type(Type::KernelVersion)?;
+        .ok()
+        .and_then(|shell| Some(shell.split('/').next_back()?.to_owned()))
+        .ok_or(InfoError::Missing("SHELL environment variable"))?;
+    let kernel = get_by_type(Type::KernelVersion).ok_or(InfoError::Missing("kernel version"))?;
 
-    let total_mem = minf_get_gb(&meminfo, "MemTotal");
-    let cached_mem = minf_get_gb(&meminfo, "Cached");
-    let available_mem = minf_get_gb(&meminfo, "MemAvailable");
+    let meminfo = parse_meminfo(&meminfo);
+    let memory = parse_memory(&meminfo, config)?;
+    let (total_swap, used_swap) = if config.include_swap {
+        let format_bytes = |bytes: u64| config.format_kb(bytes as f64 / 1024.0);
+        (
+            Some(format_bytes(memory.swap_total_bytes)),
+            Some(format_bytes(
+                memory
+                    .swap_total_bytes
+                    .saturating_sub(memory.swap_free_bytes),
+            )),
+        )
+    } else {
+        (None, None)
+    };
 
-    let total_kb: f64 = parse_minf_key(&meminfo, "MemTotal")?.parse().unwrap();
-    let available_kb: f64 = parse_minf_key(&meminfo, "MemAvailable")?.parse().unwrap();
-    let used_mem = kb_to_gb(total_kb - available_kb);
+    let boot_epoch = boot_epoch();
+    let uptime = match boot_epoch.filter(|_| config.uptime_from_boot_epoch) {
+        Some(boot_epoch) => {
+            let now = fetched_at.duration_since(UNIX_EPOCH).unwrap_or_default();
+            uptime_from_seconds(now.as_secs().saturating_sub(boot_epoch) as u32)
+        }
+        None => parse_uptime(&uptime),
+    };
+    let root_fs = get_root_fs();
+    let root_readonly = root_fs
+        .as_ref()
+        .is_some_and(|root_fs| root_fs.mount_options.iter().any(|option| option == "ro"));
 
-    let uptime = get_uptime();
+    let batteries = get_batteries();
+    let battery_health_percent = batteries.first().and_then(|battery| battery.health_percent);
+    let (cpu_pcores, cpu_ecores) = get_hybrid_core_counts();
+    let (numa_nodes, numa_node_mem) = get_numa_nodes();
+    let mut interfaces = get_interfaces();
+    let vpn_interfaces = get_vpn_interfaces(&interfaces);
+    let ipv6_enabled = get_ipv6_enabled(&interfaces);
+    if !config.include_virtual_ifaces {
+        interfaces.retain(|interface| !interface.is_virtual);
+    }
 
-    Some(SystemInfo {
+    let mut info = SystemInfo {
         distro_name,
         distro_id,
         distro_build_id,
+        distro_logo,
+        distro_home_url,
         username,
         hostname,
         shell,
@@ -221,9 +4117,357 @@ pub fn get_system_information() -> Option<SystemInfo> {
         uptime_hours: uptime.hours,
         uptime_days: uptime.days,
         uptime_formatted: uptime.formatted,
-        total_mem,
-        cached_mem,
-        available_mem,
-        used_mem,
-    })
+        total_mem: memory.total_mem,
+        cached_mem: memory.cached_mem,
+        available_mem: memory.available_mem,
+        used_mem: memory.used_mem,
+        container_runtime: get_container_runtime(),
+        gpu_temp_celsius: get_gpu_temp(config),
+        chassis_type: get_chassis_type(&cpuinfo, !batteries.is_empty()),
+        batteries,
+        available_entropy: read_sysfs("/proc/sys/kernel/random/entropy_avail")
+            .and_then(|entropy| entropy.parse().ok()),
+        disks: get_disks(),
+        bootloader: get_bootloader(),
+        swappiness: sysctl("vm.swappiness").and_then(|swappiness| swappiness.parse().ok()),
+        primary_gpu: get_primary_gpu(),
+        cpu_microcode: parse_cpuinfo_key(&cpuinfo, "microcode"),
+        booted_ago: format_booted_ago(uptime.seconds),
+        root_fs,
+        wsl: get_wsl_version(),
+        cpu_family: parse_cpuinfo_num(&cpuinfo, "cpu family"),
+        cpu_model_num: parse_cpuinfo_num(&cpuinfo, "model"),
+        cpu_stepping: parse_cpuinfo_num(&cpuinfo, "stepping"),
+        systemd_target: get_systemd_target(config),
+        open_fds: read_sysfs("/proc/sys/fs/file-nr")
+            .and_then(|file_nr| file_nr.split_whitespace().next()?.parse().ok()),
+        max_fds: read_sysfs("/proc/sys/fs/file-max").and_then(|file_max| file_max.parse().ok()),
+        dpi: get_dpi(config),
+        terminal_font: get_terminal_font(),
+        cpu_mitigations: get_cpu_mitigations(),
+        last_update: get_last_update(),
+        physical_mem_total: get_physical_mem_total(config),
+        total_swap,
+        used_swap,
+        root_readonly,
+        cpu_quota: get_cpu_quota(),
+        mem_limit: get_mem_limit(),
+        monitors: get_monitors(config),
+        smt_enabled: get_smt_enabled(&cpuinfo),
+        mac_system: get_mac_system(),
+        interfaces,
+        keyboard_layout: get_keyboard_layout(config),
+        cpu_affinity_count: get_cpu_affinity_count(),
+        fetched_at,
+        vpn_active: !vpn_interfaces.is_empty(),
+        vpn_interfaces,
+        cpu_boost: get_cpu_boost(),
+        power_profile: get_power_profile(config),
+        numa_nodes,
+        numa_node_mem,
+        cpu_pcores,
+        cpu_ecores,
+        load_per_core: get_load_per_core(&cpuinfo),
+        distro_ascii,
+        cpu_freqs_mhz: get_cpu_freqs(),
+        boot_time_breakdown: get_boot_timing(config),
+        cpu_cstates: get_cpu_cstates(),
+        boot_epoch,
+        thp: get_thp(),
+        cpu_online: count_cpu_list("/sys/devices/system/cpu/online"),
+        cpu_possible: count_cpu_list("/sys/devices/system/cpu/possible"),
+        reclaimable_mem: memory.reclaimable_mem,
+        kernel_preempt: get_by_type(Type::KernelBuild)
+            .and_then(|build| parse_kernel_preempt(&build)),
+        block_devices: get_block_devices(),
+        default_gateway: get_default_gateway(),
+        fqdn,
+        cpu_core_temps: get_cpu_core_temps(),
+        hugepages_total: meminfo.get("HugePages_Total").copied(),
+        hugepages_free: meminfo.get("HugePages_Free").copied(),
+        hugepage_size: meminfo.get("Hugepagesize").map(|kb| kb * 1024),
+        gpu_usage_percent: get_gpu_usage(config),
+        distro_ansi_color: parse_osr_key(&os_release, "ANSI_COLOR"),
+        swap_cached: meminfo.get("SwapCached").map(|kb| kb * 1024),
+        dirty: meminfo.get("Dirty").map(|kb| kb * 1024),
+        writeback: meminfo.get("Writeback").map(|kb| kb * 1024),
+        on_ac_power: get_on_ac_power(),
+        distro_variant: parse_osr_key(&os_release, "VARIANT"),
+        distro_variant_id: parse_osr_key(&os_release, "VARIANT_ID"),
+        distro_image_id: parse_osr_key(&os_release, "IMAGE_ID"),
+        distro_image_version: parse_osr_key(&os_release, "IMAGE_VERSION"),
+        flatpak_version: get_flatpak_version(config),
+        snap_version: get_snap_version(config),
+        dns_servers: get_dns_servers(),
+        kernel_cmdline_params: kernel_cmdline
+            .as_deref()
+            .map(parse_kernel_cmdline)
+            .unwrap_or_default(),
+        kernel_cmdline,
+        battery_health_percent,
+        ipv6_enabled,
+        opengl_renderer: get_opengl_renderer(config),
+        vulkan_devices: get_vulkan_devices(config),
+        wm_theme: get_wm_theme(),
+        pid_max: sysctl("kernel.pid_max").and_then(|pid_max| pid_max.parse().ok()),
+        firewall: get_firewall(config),
+        l2_shared_by: get_cache_shared_by(2),
+        l3_shared_by: get_cache_shared_by(3),
+    };
+
+    if config.sanitize {
+        info.sanitize_fields();
+    }
+
+    Ok(info)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kernel_versions_order_by_patch_level() {
+        assert!(KernelVersion::new(6, 1, 2) < KernelVersion::new(6, 1, 10));
+        assert!(KernelVersion::new(6, 2, 0) > KernelVersion::new(6, 1, 99));
+        assert_eq!(KernelVersion::new(6, 1, 0), KernelVersion::new(6, 1, 0));
+    }
+
+    #[test]
+    fn kernel_version_defaults_missing_patch_to_zero() {
+        let info = SystemInfo {
+            kernel: "6.1-arch1".into(),
+            ..SystemInfo::empty()
+        };
+        assert_eq!(info.kernel_version(), Some(KernelVersion::new(6, 1, 0)));
+
+        let info = SystemInfo {
+            kernel: "5.15.90.1-microsoft-standard-WSL2".into(),
+            ..SystemInfo::empty()
+        };
+        assert_eq!(info.kernel_version(), Some(KernelVersion::new(5, 15, 90)));
+    }
+
+    #[test]
+    fn empty_has_only_blank_fields() {
+        let info = SystemInfo::empty();
+        for (name, value) in info.as_pairs() {
+            assert!(
+                ["", "0", "false", "0 0 0"].contains(&value.as_str()),
+                "{name} is {value:?}"
+            );
+        }
+        assert_eq!(info.fetched_at, UNIX_EPOCH);
+    }
+
+    #[test]
+    fn meminfo_raw_converts_kilobytes_to_bytes() {
+        let meminfo = parse_meminfo_raw("MemTotal:       2 kB\nHugePages_Total:       3\n");
+        assert_eq!(meminfo["MemTotal"], 2048);
+        assert_eq!(meminfo["HugePages_Total"], 3);
+    }
+
+    #[test]
+    fn key_value_file_handles_quoting() {
+        let contents = concat!(
+            "VERSION_ID=12\n",
+            "ID=debian\n",
+            "NAME=\"Debian GNU/Linux\"\n",
+            "PRETTY_NAME='Debian 12'\n",
+            "ESCAPED=\"say \\\"hi\\\"\"\n",
+            "MULTI=\"first\n",
+            "second\"\n",
+            "BROKEN=\"never closed\n",
+        );
+        let value = |key| parse_key_value_file(contents, key);
+        assert_eq!(value("ID").as_deref(), Some("debian"));
+        assert_eq!(value("NAME").as_deref(), Some("Debian GNU/Linux"));
+        assert_eq!(value("PRETTY_NAME").as_deref(), Some("Debian 12"));
+        assert_eq!(value("ESCAPED").as_deref(), Some("say \"hi\""));
+        assert_eq!(value("MULTI").as_deref(), Some("first\nsecond"));
+        assert_eq!(value("BROKEN").as_deref(), Some("never closed"));
+        assert_eq!(value("MISSING"), None);
+    }
+
+    /// Runs with and without the `byte-unit` feature, so both implementations must agree.
+    #[test]
+    fn kb_formatting_is_the_same_with_and_without_byte_unit() {
+        assert_eq!(kb_to_gb(15_625_000.0), "15.62 GB");
+        assert_eq!(kb_to_gb(0.0), "0.00 GB");
+        assert_eq!(kb_to_gb(-5.0), "0.00 GB");
+
+        let (value, unit) = kb_to_parts(16_777_216.0, MemUnit::Binary);
+        assert_eq!((format!("{value:.2}"), unit), ("16.00".to_owned(), "GiB"));
+        let (value, unit) = kb_to_parts(2_000_000.0, MemUnit::Decimal);
+        assert_eq!((format!("{value:.2}"), unit), ("2.00".to_owned(), "GB"));
+    }
+
+    #[test]
+    fn fetched_at_is_recent() {
+        let sources = Sources {
+            os_release: "NAME=Test\nID=test\nBUILD_ID=rolling\n".to_owned(),
+            meminfo: "MemTotal: 2048 kB\nCached: 512 kB\nMemAvailable: 1024 kB\n".to_owned(),
+            uptime: "3600.00 7200.00\n".to_owned(),
+            cpuinfo: String::new(),
+        };
+        let info = match build_system_information(sources, &Config::default()) {
+            Ok(info) => info,
+            // The username, hostname and shell come from the environment running the tests.
+            Err(InfoError::Missing(_) | InfoError::Unsupported) => return,
+            Err(error) => panic!("{error}"),
+        };
+
+        let age = SystemTime::now()
+            .duration_since(info.fetched_at)
+            .expect("fetched_at is in the future");
+        assert!(age < Duration::from_secs(5), "fetched_at is {age:?} old");
+    }
+
+    #[test]
+    fn sysctl_rejects_paths_outside_proc_sys() {
+        assert_eq!(sysctl("../etc/passwd"), None);
+        assert_eq!(sysctl("vm..swappiness"), None);
+        assert_eq!(sysctl("vm/swappiness"), None);
+        assert_eq!(sysctl(""), None);
+    }
+
+    #[test]
+    fn pid_max_is_read_through_sysctl() {
+        if !Path::new("/proc/sys/kernel/pid_max").exists() {
+            return;
+        }
+
+        let pid_max: u32 = sysctl("kernel.pid_max").unwrap().parse().unwrap();
+        assert!(pid_max > 0);
+    }
+
+    #[test]
+    fn sanitize_fields_reaches_nested_strings() {
+        let mut info = SystemInfo {
+            distro_ansi_color: Some("1\x1b]0;pwned\x07".into()),
+            monitors: vec![MonitorInfo {
+                manufacturer: "DEL".into(),
+                model: "U2720Q\x1b[2J".into(),
+                serial: Some("\x07123".into()),
+                size_mm: (600, 340),
+                color_depth: None,
+            }],
+            ..SystemInfo::empty()
+        };
+        info.sanitize_fields();
+        assert_eq!(info.distro_ansi_color.as_deref(), Some("1"));
+        assert_eq!(info.monitors[0].model, "U2720Q");
+        assert_eq!(info.monitors[0].serial.as_deref(), Some("123"));
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn command_output_kills_hung_programs() {
+        assert_eq!(command_output(&Config::default(), "echo", &["hi"]), None);
+
+        let config = Config {
+            allow_subprocess: true,
+            ..Config::default()
+        };
+        assert_eq!(
+            command_output(&config, "echo", &["hi"]).as_deref(),
+            Some("hi")
+        );
+        assert_eq!(command_output(&config, "false", &[]), None);
+
+        let started = Instant::now();
+        assert_eq!(command_output(&config, "sleep", &["30"]), None);
+        assert!(started.elapsed() < COMMAND_TIMEOUT + Duration::from_secs(2));
+    }
+
+    #[test]
+    fn volatile_fields_exist() {
+        let pairs = SystemInfo::empty().as_pairs();
+        for field in SystemInfo::volatile_fields() {
+            assert!(
+                pairs.iter().any(|(name, _)| name == field),
+                "{field} isn't a field"
+            );
+        }
+    }
+
+    #[test]
+    fn edid_color_depth_needs_version_1_4() {
+        let mut edid = [0; 128];
+        edid[..8].copy_from_slice(&[0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00]);
+        // Digital input with 8 bits per color channel.
+        edid[20] = 0xA0;
+
+        edid[18..20].copy_from_slice(&[1, 4]);
+        assert_eq!(parse_edid(&edid).unwrap().color_depth, Some(24));
+        edid[18..20].copy_from_slice(&[1, 3]);
+        assert_eq!(parse_edid(&edid).unwrap().color_depth, None);
+    }
+
+    #[test]
+    fn binary_mem_matches_the_byte_counts() {
+        let meminfo = HashMap::from([
+            ("MemTotal", 16_000_000),
+            ("Cached", 4_000_000),
+            ("MemAvailable", 8_000_000),
+        ]);
+        let config = Config {
+            mem_unit: MemUnit::Binary,
+            ..Config::default()
+        };
+        let memory = parse_memory(&meminfo, &config).unwrap();
+        let gib = |bytes| format!("{:.2} GiB", bytes as f64 / 1_073_741_824.0);
+        assert_eq!(memory.total_mem, gib(memory.total_bytes));
+        assert_eq!(memory.used_mem, gib(memory.used_bytes));
+        assert_eq!(memory.total_mem, "15.26 GiB");
+    }
+
+    #[test]
+    fn overall_percentage_weights_only_matching_capacities() {
+        let battery = |percentage, energy_full, charge_full| Battery {
+            name: "BAT0".into(),
+            percentage,
+            status: "Discharging".into(),
+            energy_full,
+            charge_full,
+            health_percent: None,
+        };
+
+        let energy = [
+            battery(100.0, Some(30_000_000), None),
+            battery(0.0, Some(10_000_000), None),
+        ];
+        assert_eq!(overall_percentage(&energy), Some(75.0));
+
+        let mixed = [
+            battery(100.0, Some(30_000_000), None),
+            battery(0.0, None, Some(10_000_000)),
+        ];
+        assert_eq!(overall_percentage(&mixed), Some(50.0));
+        assert_eq!(overall_percentage(&[]), None);
+    }
+
+    #[test]
+    fn hosts_fqdn_needs_the_hostname_and_a_domain() {
+        let hosts = "127.0.0.1 localhost\n# 10.0.0.1 box.commented.out\n\
+                     127.0.1.1\tbox.example.com box\n";
+        assert_eq!(
+            parse_hosts_fqdn(hosts, "box").as_deref(),
+            Some("box.example.com")
+        );
+        assert_eq!(parse_hosts_fqdn(hosts, "bo"), None);
+        assert_eq!(parse_hosts_fqdn("127.0.1.1 box. box\n", "box"), None);
+    }
+
+    #[test]
+    fn sample_waits_at_least_the_minimum_interval() {
+        let sample = sample(Duration::ZERO);
+        assert!(sample.elapsed >= MIN_SAMPLE_INTERVAL);
+        for rate in [sample.rx_bytes_per_sec, sample.tx_bytes_per_sec]
+            .into_iter()
+            .flatten()
+        {
+            assert!(rate.is_finite());
+        }
+    }
 }